@@ -1,17 +1,23 @@
 //! HTTP Client
 
-use crate::error::{Error, RequestNotSuccessful};
+use crate::{
+    error::{ConnectAPIError, Error, RetryAttempt, RetryError},
+    secret::SecretString,
+};
 use async_trait::async_trait;
 use dotenv::dotenv;
 use exponential_backoff::Backoff;
 use hyper::{
-    client::connect::HttpConnector, header::HeaderValue, Body, Client as HyperClient, Method,
-    Response, StatusCode,
+    body::Bytes,
+    client::connect::HttpConnector,
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Body, Client as HyperClient, Method, Response as HyperResponse, StatusCode,
 };
 use hyper_rustls::HttpsConnector;
 use log::{debug, error};
 use serde_json::Value;
-use std::{fmt, ops, thread, time::Duration};
+use std::{future::Future, ops, time::Duration};
+use zeroize::Zeroizing;
 
 /// GET method
 pub const GET: Method = Method::GET;
@@ -19,19 +25,197 @@ pub const GET: Method = Method::GET;
 pub const POST: Method = Method::POST;
 /// PUT method
 pub const PUT: Method = Method::PUT;
+/// PATCH method
+pub const PATCH: Method = Method::PATCH;
 /// DELETE method
 pub const DELETE: Method = Method::DELETE;
 
 const RETRY_ATTEMPTS: u32 = 5;
 
+/// Request headers set by the client, which extra headers may not replace.
+const RESERVED_HEADERS: [&str; 2] = ["accept", "authorization"];
+
+/// Response headers that carry the server's request ID, in order of preference.
+const REQUEST_ID_HEADERS: [&str; 2] = ["x-request-id", "x-correlation-id"];
+
 /// Represents a (Hyper) HTTP client.
 #[derive(Debug)]
 pub struct Client {
-    api_key: String,
+    api_key: SecretString,
     server_url: String,
     https_client: HyperClient<HttpsConnector<HttpConnector>>,
 }
 
+/// Options applied to a single API call.
+///
+/// Every function in [`vaults`](crate::vaults) and [`items`](crate::items) has a `_with_options`
+/// variant accepting these; the plain variants use [`CallOptions::default`], which keeps the
+/// client's retry behaviour and applies no timeouts.
+///
+/// Dropping the future returned by an API call aborts the in-flight request.
+///
+/// ```
+/// use connect_1password::client::CallOptions;
+/// use std::time::Duration;
+///
+/// let options = CallOptions::new()
+///     .deadline(Duration::from_secs(10))
+///     .attempt_timeout(Duration::from_secs(2))
+///     .retries(2)
+///     .header("X-Correlation-Id", "abc123");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CallOptions {
+    /// Upper bound for the whole call, including retries and reading the response body.
+    pub deadline: Option<Duration>,
+    /// Upper bound for each individual attempt at sending the request.
+    pub attempt_timeout: Option<Duration>,
+    /// Number of times a failed attempt is retried, overriding the client's default; 0 makes a
+    /// single attempt.
+    pub retries: Option<u32>,
+    /// Extra headers sent along with the request. `Accept` and `Authorization` are set by the
+    /// client and can't be overridden.
+    pub headers: Vec<(String, String)>,
+}
+
+impl CallOptions {
+    /// Create a new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify the deadline for the whole call
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Specify the timeout for each attempt
+    pub fn attempt_timeout(mut self, timeout: Duration) -> Self {
+        self.attempt_timeout = Some(timeout);
+        self
+    }
+
+    /// Specify how many times a failed attempt is retried; 0 disables retries.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+
+    /// Add an extra header
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Run `future` to completion, failing with a timeout error if `limit` elapses first.
+async fn with_timeout<F, T>(limit: Option<Duration>, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, future)
+            .await
+            .map_err(|_| Error::new_timeout_error())?,
+        None => future.await,
+    }
+}
+
+/// A successful response from the Connect API.
+///
+/// Dereferences to the decoded value. The raw body is kept as bytes and only parsed again when
+/// [`Response::json`] is called.
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    value: T,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl<T> Response<T> {
+    /// Create a new instance
+    pub fn new(value: T, status: StatusCode, headers: HeaderMap, body: Bytes) -> Self {
+        Self {
+            value,
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// Returns a reference to the decoded value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Consumes the response, returning the decoded value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Returns the HTTP status code.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the request ID assigned by the server, if one was sent.
+    pub fn request_id(&self) -> Option<&str> {
+        request_id(&self.headers)
+    }
+
+    /// Returns the raw response body.
+    pub fn bytes(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Parses the raw response body into an untyped JSON value. An empty body parses as an empty
+    /// object, as it does when decoding the value.
+    pub fn json(&self) -> Result<Value, Error> {
+        serde_json::from_slice(json_body(&self.body)).map_err(Error::new_parsing_error)
+    }
+
+    /// Maps the decoded value, keeping the response metadata.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Response<U> {
+        Response {
+            value: f(self.value),
+            status: self.status,
+            headers: self.headers,
+            body: self.body,
+        }
+    }
+}
+
+impl<T> ops::Deref for Response<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Empty bodies (e.g. on DELETE) decode as an empty object
+fn json_body(body: &[u8]) -> &[u8] {
+    if body.is_empty() {
+        b"{}"
+    } else {
+        body
+    }
+}
+
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<&str> {
+    REQUEST_ID_HEADERS
+        .iter()
+        .find_map(|name| headers.get(*name))
+        .and_then(|value| value.to_str().ok())
+}
+
 /// Interface for any compatible HTTP client
 #[async_trait]
 pub trait HTTPClient {
@@ -42,7 +226,8 @@ pub trait HTTPClient {
         endpoint: &str,
         params: &[(&str, &str)],
         body: Option<String>,
-    ) -> Result<(T, Value), Error>
+        options: &CallOptions,
+    ) -> Result<Response<T>, Error>
     where
         T: serde::de::DeserializeOwned + std::fmt::Debug;
 }
@@ -55,70 +240,76 @@ impl HTTPClient for Client {
         endpoint: &str,
         params: &[(&str, &str)],
         body: Option<String>,
-    ) -> Result<(T, Value), Error>
+        options: &CallOptions,
+    ) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        with_timeout(
+            options.deadline,
+            self.send_request_inner(method, endpoint, params, body, options),
+        )
+        .await
+    }
+}
+
+impl Client {
+    async fn send_request_inner<T>(
+        &self,
+        method: &str,
+        endpoint: &str,
+        params: &[(&str, &str)],
+        body: Option<String>,
+        options: &CallOptions,
+    ) -> Result<Response<T>, Error>
     where
         T: serde::de::DeserializeOwned + std::fmt::Debug,
     {
-        let api_key: &String = &self.api_key;
+        let api_key = self.api_key.expose();
 
         let method = match method {
             "GET" => GET,
             "POST" => POST,
             "PUT" => PUT,
+            "PATCH" => PATCH,
             "DELETE" => DELETE,
             &_ => GET,
         };
 
-        let resp = retry_with_backoff(self, &method, &api_key[..], endpoint, params, body).await?;
-        let status = resp.status();
+        let (resp, attempts) =
+            retry_with_backoff(self, &method, api_key, endpoint, params, body, options).await?;
+        let (parts, body) = resp.into_parts();
+        let status = parts.status;
+        let request_id = request_id(&parts.headers);
+        let with_metadata = |err: Error| err.with_attempts(attempts).with_request_id(request_id);
 
-        let data: (Result<T, Error>, Value) = hyper::body::to_bytes(resp.into_body())
+        let bytes = hyper::body::to_bytes(body)
             .await
             .map_err(Error::new_network_error)
-            .map(|mut bytes| {
-                dbg!(&bytes);
-
-                if &bytes.len() == &0 {
-                    bytes = hyper::body::Bytes::from("{}");
-                }
-                let json = serde_json::from_slice(&bytes).map_err(Error::new_parsing_error);
-
-                (json, bytes)
-            })
-            .and_then(|data| {
-                let json = data.0;
-                let bytes = std::str::from_utf8(&data.1)?;
-                let json_raw: Value = dbg!(serde_json::from_str(bytes)?);
-
-                dbg!(&json);
-                dbg!(&json_raw);
-
-                match status {
-                    StatusCode::OK => {}
-                    StatusCode::NO_CONTENT => {}
-                    _ => {
-                        debug!(
-                            "Client error! Status: {}, JSON: {}",
-                            status,
-                            &bytes.to_string()
-                        );
-
-                        return Err(RequestNotSuccessful::new(status, bytes.to_string()).into());
-                    }
-                };
-
-                Ok((json, json_raw))
-            })?;
-        let decoded = data.0?;
-        let raw_json = data.1;
+            .map_err(with_metadata)?;
+
+        match status {
+            StatusCode::OK => {}
+            StatusCode::NO_CONTENT => {}
+            _ => {
+                debug!(
+                    "Client error! Status: {}, JSON: {}",
+                    status,
+                    String::from_utf8_lossy(&bytes)
+                );
+
+                let err = ConnectAPIError::from_response(status, &bytes);
+                return Err(with_metadata(Error::new_connect_error(err)));
+            }
+        };
 
-        dbg!(&decoded);
+        let decoded: T = serde_json::from_slice(json_body(&bytes))
+            .map_err(Error::new_parsing_error)
+            .map_err(with_metadata)?;
 
-        Ok((decoded, raw_json))
+        Ok(Response::new(decoded, status, parts.headers, bytes))
     }
-}
 
-impl Client {
     /// Create a new instance
     ///
     /// # Fields
@@ -134,20 +325,28 @@ impl Client {
             .build();
 
         Self {
-            api_key: token.to_string(),
+            api_key: SecretString::from(token),
             server_url: server_url.to_string(),
             https_client: hyper::Client::builder().build::<_, hyper::Body>(https),
         }
     }
 
+    /// Returns the 1Password Connect API token.
+    pub fn token(&self) -> &SecretString {
+        &self.api_key
+    }
+}
+
+impl Default for Client {
     /// Create an instance by fetching defaults from the host ENV.
     ///
     /// # Fields
     ///
     /// - `OP_API_TOKEN`: provide the 1Password Connect API token.
     /// - `OP_SERVER_URL`: provide full URL to the host server, i.e. `http://localhost:8080`
-    pub fn default() -> Self {
-        let token = std::env::var("OP_API_TOKEN").expect("1Password API token expected!");
+    fn default() -> Self {
+        let token =
+            Zeroizing::new(std::env::var("OP_API_TOKEN").expect("1Password API token expected!"));
         let host = std::env::var("OP_SERVER_URL").expect("1Password Connect server URL expected!");
 
         // .env to override settings in ENV
@@ -155,32 +354,14 @@ impl Client {
 
         Client::new(&token, &host)
     }
-
-    /// Returns the 1Password Connect API token.
-    pub fn token(&self) -> String {
-        self.api_key.clone()
-    }
-}
-
-struct RetryErrors<'a>(pub(crate) &'a mut Vec<String>);
-
-impl<'a> fmt::Display for RetryErrors<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.iter().fold(Ok(()), |result, error_msg| {
-            result.and_then(|_| writeln!(f, "{}", error_msg))
-        })
-    }
-}
-
-impl ops::Deref for RetryErrors<'_> {
-    type Target = Vec<String>;
-
-    fn deref(&self) -> &Self::Target {
-        self.0
-    }
 }
 
-/// Attempt exponential backoff when re-attempting requests to the Melissa service.
+/// Attempt exponential backoff when re-attempting requests to the Connect API.
+///
+/// A POST or PATCH may have reached the server once the connection is established, so it is only
+/// retried if connecting failed.
+///
+/// Returns the response along with the number of attempts it took.
 async fn retry_with_backoff(
     client: &Client,
     method: &hyper::Method,
@@ -188,15 +369,29 @@ async fn retry_with_backoff(
     endpoint: &str,
     params: &[(&str, &str)],
     body: Option<String>,
-) -> Result<Response<Body>, Error> {
-    let retries = RETRY_ATTEMPTS;
+    options: &CallOptions,
+) -> Result<(HyperResponse<Body>, u32), Error> {
+    let reserved = options.headers.iter().find(|(name, _)| {
+        RESERVED_HEADERS
+            .iter()
+            .any(|reserved| name.eq_ignore_ascii_case(reserved))
+    });
+    if let Some((name, _)) = reserved {
+        return Err(Error::new_reserved_header_error(name));
+    }
+
+    let idempotent = !matches!(*method, Method::POST | Method::PATCH);
+    let retries = options.retries.unwrap_or(RETRY_ATTEMPTS);
     let min = Duration::from_millis(100);
     let max = Duration::from_secs(20);
-    let backoff = Backoff::new(retries, min, max);
-    let mut retry_error_messages: Vec<String> = vec![];
-    let mut retry_errors = vec![];
-
-    for duration in &backoff {
+    // The backoff yields `retries + 1` durations but doesn't accept 0 retries
+    let backoff = Backoff::new(retries.max(1), min, max);
+    let mut durations = backoff.iter().take(retries as usize + 1).peekable();
+    let mut failed_attempts = vec![];
+    let mut attempts = 0;
+
+    while let Some(duration) = durations.next() {
+        attempts += 1;
         let url = format!("{}/{}?{}", client.server_url, endpoint, url_encode(params));
 
         let body_data = match body {
@@ -208,32 +403,56 @@ async fn retry_with_backoff(
             .uri(&*url)
             .body(body_data)?;
 
-        let auth = String::from("Bearer ") + api_key;
+        let auth = Zeroizing::new(String::from("Bearer ") + api_key);
+        let mut auth_value = HeaderValue::from_str(&auth)?;
+        auth_value.set_sensitive(true);
         req.headers_mut()
             .insert("Accept", HeaderValue::from_str("application/json")?);
-        req.headers_mut()
-            .insert("Authorization", HeaderValue::from_str(&auth)?);
+        req.headers_mut().insert("Authorization", auth_value);
+        for (name, value) in &options.headers {
+            req.headers_mut().append(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+
+        let attempt = async {
+            client
+                .https_client
+                .request(req)
+                .await
+                .map_err(Error::new_network_error)
+        };
 
-        match client.https_client.request(req).await {
-            Ok(value) => return Ok(value),
+        match with_timeout(options.attempt_timeout, attempt).await {
+            Ok(value) => return Ok((value, attempts)),
             Err(err) => {
-                let error_message = format!("[ Retrying ]: Client error: {}", err);
-                retry_error_messages.push(error_message);
-                retry_errors.push(err);
+                // A POST or PATCH which may have been written is not sent again
+                let resendable = idempotent || never_connected(&err);
+                let last = durations.peek().is_none() || !resendable;
+                // No point in waiting once the last attempt has failed
+                let wait = if last { Duration::ZERO } else { duration };
+                debug!("[ Retrying ]: Client error: {}", err);
+                failed_attempts.push(RetryAttempt::new(err, wait));
+                if last {
+                    break;
+                }
 
-                thread::sleep(duration)
+                tokio::time::sleep(wait).await
             }
         }
     }
 
-    let err = if let Some(val) = retry_errors.pop() {
-        val
-    } else {
-        error!("Unable to unwrap error");
-        return Err(Error::new_internal_error());
-    };
+    let retry_error = RetryError::new(failed_attempts);
+    error!("{}", retry_error);
+
+    Err(Error::new_retry_error(retry_error).with_attempts(attempts))
+}
 
-    Err(Error::new_retry_error(err))
+/// Whether the attempt failed before a connection was established, so the request was never sent.
+fn never_connected(err: &Error) -> bool {
+    err.find_source::<hyper::Error>()
+        .is_some_and(hyper::Error::is_connect)
 }
 
 fn url_encode(params: &[(&str, &str)]) -> String {
@@ -249,3 +468,278 @@ fn url_encode(params: &[(&str, &str)]) -> String {
             acc.replace('+', "%2B")
         })
 }
+
+#[cfg(test)]
+mod test {
+    use super::{CallOptions, Client, HTTPClient};
+    use crate::{error::ErrorKind, items, models::item::ItemBuilder, vaults};
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Server, StatusCode,
+    };
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::{Duration, Instant},
+    };
+    use tokio::{io::AsyncReadExt, net::TcpListener, test};
+
+    /// Answers every request with the same status, headers and body.
+    async fn canned_server(
+        status: u16,
+        headers: &'static [(&'static str, &'static str)],
+        body: &'static str,
+    ) -> String {
+        let make_service = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |_| async move {
+                let mut response = hyper::Response::builder().status(status);
+                for (name, value) in headers {
+                    response = response.header(*name, *value);
+                }
+
+                Ok::<_, Infallible>(response.body(Body::from(body)).unwrap())
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let address = server.local_addr();
+        tokio::spawn(server);
+
+        format!("http://{}", address)
+    }
+
+    /// Accepts connections but never answers them.
+    async fn silent_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut connections = vec![];
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    /// Reads each request, then drops the connection without answering. Returns the number of
+    /// requests received.
+    async fn dropping_server() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buffer = [0; 4096];
+                if socket.read(&mut buffer).await.unwrap_or(0) > 0 {
+                    received.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        });
+
+        (format!("http://{}", address), requests)
+    }
+
+    #[test]
+    async fn deadline_aborts_hung_call() {
+        let client = Client::new("token", &silent_server().await);
+        let options = CallOptions::new().deadline(Duration::from_millis(200));
+
+        let started = Instant::now();
+        let err = vaults::all_with_options(&client, &options)
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(err.is_timeout());
+    }
+
+    #[test]
+    async fn attempt_timeout_is_retried() {
+        let client = Client::new("token", &silent_server().await);
+        let options = CallOptions::new()
+            .attempt_timeout(Duration::from_millis(50))
+            .retries(2);
+
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let item_id = "2fcbqwe9ndg175zg2dzwftvkpa".parse().unwrap();
+        let err = items::get_with_options(&client, &vault_id, &item_id, &options)
+            .await
+            .unwrap_err();
+
+        assert!(err.is_timeout());
+        assert_eq!(err.context().attempts, Some(3));
+
+        let retry_error = err.retry_error().unwrap();
+        assert_eq!(retry_error.attempts.len(), 3);
+        assert!(retry_error.attempts.iter().all(|a| a.error.is_timeout()));
+        assert_eq!(retry_error.attempts[2].wait, Duration::ZERO);
+        assert!(retry_error.attempts[0].failed_at <= retry_error.attempts[2].failed_at);
+        assert_eq!(
+            err.context().vault_id.as_deref(),
+            Some("ytrfte14kw1uex5txn3pnaoerm")
+        );
+        assert_eq!(
+            err.context().item_id.as_deref(),
+            Some("2fcbqwe9ndg175zg2dzwftvkpa")
+        );
+    }
+
+    #[test]
+    async fn timed_out_post_is_not_resent() {
+        let client = Client::new("token", &silent_server().await);
+        let options = CallOptions::new()
+            .attempt_timeout(Duration::from_millis(50))
+            .retries(2);
+
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let item = ItemBuilder::login(&vault_id)
+            .title("Test login item")
+            .username("Bob")
+            .password("hunter2")
+            .build()
+            .unwrap();
+        let err = items::add_with_options(&client, item, &options)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.context().attempts, Some(1));
+        assert!(err.retry_error().unwrap().attempts[0].error.is_timeout());
+    }
+
+    #[test]
+    async fn dropped_post_is_not_resent() {
+        let (server, requests) = dropping_server().await;
+        let client = Client::new("token", &server);
+        let options = CallOptions::new().retries(2);
+
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let item = ItemBuilder::login(&vault_id)
+            .title("Test login item")
+            .username("Bob")
+            .password("hunter2")
+            .build()
+            .unwrap();
+        let err = items::add_with_options(&client, item, &options)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.context().attempts, Some(1));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    async fn refused_post_is_retried() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let client = Client::new("token", &format!("http://{}", address));
+        let options = CallOptions::new().retries(1);
+
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let item = ItemBuilder::login(&vault_id)
+            .title("Test login item")
+            .username("Bob")
+            .password("hunter2")
+            .build()
+            .unwrap();
+        let err = items::add_with_options(&client, item, &options)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.context().attempts, Some(2));
+    }
+
+    #[test]
+    async fn zero_retries_makes_one_attempt() {
+        let client = Client::new("token", &silent_server().await);
+        let options = CallOptions::new()
+            .attempt_timeout(Duration::from_millis(50))
+            .retries(0);
+
+        let err = vaults::all_with_options(&client, &options)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.context().attempts, Some(1));
+    }
+
+    #[test]
+    async fn reserved_headers_are_rejected() {
+        let server = canned_server(200, &[], "[]").await;
+        let client = Client::new("token", &server);
+        let options = CallOptions::new().header("authorization", "Bearer other");
+
+        let err = vaults::all_with_options(&client, &options)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidRequest);
+        assert!(err.to_string().contains("authorization"));
+
+        let options = CallOptions::new().header("X-Correlation-Id", "abc123");
+        assert!(vaults::all_with_options(&client, &options).await.is_ok());
+    }
+
+    #[test]
+    async fn response_keeps_metadata() {
+        let body = r#"[{"id":"2fcbqwe9ndg175zg2dzwftvkpa","title":"Dell XYZ","vault":{"id":"ytrfte14kw1uex5txn3pnaoerm"}}]"#;
+        let server = canned_server(200, &[("x-request-id", "req-123")], body).await;
+        let client = Client::new("token", &server);
+
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let response = items::all(&client, &vault_id).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.request_id(), Some("req-123"));
+        assert_eq!(response.bytes().as_ref(), body.as_bytes());
+        assert_eq!(response.json().unwrap()[0]["title"], "Dell XYZ");
+        assert_eq!(response.len(), 1);
+    }
+
+    #[test]
+    async fn empty_body_is_an_object() {
+        let server = canned_server(204, &[], "").await;
+        let client = Client::new("token", &server);
+
+        let response = client
+            .send_request::<serde_json::Value>(
+                "DELETE",
+                "v1/vaults",
+                &[],
+                None,
+                &CallOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(*response.value(), serde_json::json!({}));
+        assert_eq!(response.json().unwrap(), *response.value());
+    }
+
+    #[test]
+    async fn connect_error_body_is_decoded() {
+        let body = r#"{"status":401,"message":"Invalid bearer token"}"#;
+        let server = canned_server(401, &[("x-request-id", "req-401")], body).await;
+        let client = Client::new("secret-token", &server);
+
+        let err = vaults::all(&client).await.unwrap_err();
+
+        assert!(err.is_unauthorized());
+        assert_eq!(err.context().operation, Some("vaults::all"));
+        assert_eq!(err.context().attempts, Some(1));
+        assert_eq!(err.context().request_id.as_deref(), Some("req-401"));
+        assert!(!err.to_string().contains("secret-token"));
+        assert!(!format!("{:?}", client).contains("secret-token"));
+        assert_eq!(client.token().expose(), "secret-token");
+        assert_eq!(err.kind(), ErrorKind::Api);
+        assert!(err.to_string().contains("Invalid bearer token"));
+    }
+}
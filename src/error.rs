@@ -1,12 +1,17 @@
 //! Error and Result module.
 
-use hyper::{header::InvalidHeaderValue, StatusCode};
-use regex::Regex;
+use chrono::{DateTime, Utc};
+use hyper::{
+    header::{InvalidHeaderName, InvalidHeaderValue},
+    Method, StatusCode,
+};
+use serde::Deserialize;
 use std::{
     error::Error as StdError,
     fmt::{self, Display},
     result::Result,
     str::Utf8Error,
+    time::Duration,
 };
 
 /// A simple type alias so as to DRY.
@@ -23,6 +28,89 @@ pub struct Error {
 struct ErrorImpl {
     kind: Kind,
     cause: Option<Cause>,
+    context: ErrorContext,
+}
+
+/// Describes the API call that produced an [`Error`].
+///
+/// Only identifiers and request metadata are recorded; field values and the API token never are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The SDK operation, e.g. `items::add`.
+    pub operation: Option<&'static str>,
+    /// The HTTP method of the request.
+    pub method: Option<Method>,
+    /// The endpoint's path template, e.g. `v1/vaults/{vault_id}/items`.
+    pub path: Option<&'static str>,
+    /// The vault the call was made against.
+    pub vault_id: Option<String>,
+    /// The item the call was made against.
+    pub item_id: Option<String>,
+    /// The number of attempts made to send the request.
+    pub attempts: Option<u32>,
+    /// The request ID assigned by the server.
+    pub request_id: Option<String>,
+}
+
+impl ErrorContext {
+    /// Returns true if no context has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        if let Some(operation) = self.operation {
+            parts.push(format!("operation: {}", operation));
+        }
+        match (&self.method, self.path) {
+            (Some(method), Some(path)) => parts.push(format!("request: {} {}", method, path)),
+            (Some(method), None) => parts.push(format!("request: {}", method)),
+            (None, Some(path)) => parts.push(format!("request: {}", path)),
+            (None, None) => {}
+        }
+        if let Some(ref vault_id) = self.vault_id {
+            parts.push(format!("vault_id: {}", vault_id));
+        }
+        if let Some(ref item_id) = self.item_id {
+            parts.push(format!("item_id: {}", item_id));
+        }
+        if let Some(attempts) = self.attempts {
+            parts.push(format!("attempts: {}", attempts));
+        }
+        if let Some(ref request_id) = self.request_id {
+            parts.push(format!("request_id: {}", request_id));
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Broad classification of an [`Error`], stable across releases.
+///
+/// Use [`Error::kind`] to obtain it, or one of the `is_*` predicates on [`Error`] for the most
+/// common checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The connection to the Connect server failed.
+    Transport,
+    /// The call did not complete within its deadline or attempt timeout.
+    Timeout,
+    /// The request could not be built, e.g. due to an invalid header.
+    InvalidRequest,
+    /// The Connect API answered with an unsuccessful HTTP status; see [`Error::status`].
+    Api,
+    /// The response body, or the request body, could not be (de)serialized.
+    Decode,
+    /// An item failed validation before being sent; see [`Error::validation_error`].
+    Validation,
+    /// A field looked up on an item is missing or ambiguous; see [`Error::field_error`].
+    Field,
+    /// Reading or writing a local file failed.
+    Io,
 }
 
 impl fmt::Debug for Error {
@@ -32,6 +120,9 @@ impl fmt::Debug for Error {
         if let Some(ref cause) = self.inner.cause {
             f.field(cause);
         }
+        if !self.inner.context.is_empty() {
+            f.field(&self.inner.context);
+        }
         f.finish()
     }
 }
@@ -39,10 +130,16 @@ impl fmt::Debug for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(ref cause) = self.inner.cause {
-            write!(f, "{}: {}", self.description(), cause)
+            write!(f, "{}: {}", self.description(), cause)?;
         } else {
-            f.write_str(&self.description())
+            f.write_str(&self.description())?;
+        }
+
+        if !self.inner.context.is_empty() {
+            write!(f, " ({})", self.inner.context)?;
         }
+
+        Ok(())
     }
 }
 
@@ -58,7 +155,11 @@ impl StdError for Error {
 impl Error {
     pub(super) fn new(kind: Kind) -> Error {
         Error {
-            inner: Box::new(ErrorImpl { kind, cause: None }),
+            inner: Box::new(ErrorImpl {
+                kind,
+                cause: None,
+                context: ErrorContext::default(),
+            }),
         }
     }
 
@@ -67,10 +168,71 @@ impl Error {
         self
     }
 
-    pub(crate) fn find_source<E: StdError + 'static>(&self) -> Option<&E> {
+    /// Record the operation which failed, along with its HTTP method and path template.
+    pub(crate) fn with_operation(
+        mut self,
+        operation: &'static str,
+        method: Method,
+        path: &'static str,
+    ) -> Error {
+        let context = &mut self.inner.context;
+        context.operation = Some(operation);
+        context.method = Some(method);
+        context.path = Some(path);
+        self
+    }
+
+    pub(crate) fn with_vault_id(mut self, vault_id: &str) -> Error {
+        self.inner.context.vault_id = Some(vault_id.to_string());
+        self
+    }
+
+    pub(crate) fn with_item_id(mut self, item_id: &str) -> Error {
+        self.inner.context.item_id = Some(item_id.to_string());
+        self
+    }
+
+    pub(crate) fn with_attempts(mut self, attempts: u32) -> Error {
+        self.inner.context.attempts = Some(attempts);
+        self
+    }
+
+    pub(crate) fn with_request_id(mut self, request_id: Option<&str>) -> Error {
+        self.inner.context.request_id = request_id.map(|id| id.to_string());
+        self
+    }
+
+    /// Returns the context of the call which produced this error.
+    pub fn context(&self) -> &ErrorContext {
+        &self.inner.context
+    }
+
+    /// Returns every problem found, if this error was caused by failed validation.
+    pub fn validation_error(&self) -> Option<&ValidationError> {
+        match &self.inner.kind {
+            Kind::ValidationError(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Returns the failed lookup, if this error was caused by a missing or ambiguous field.
+    pub fn field_error(&self) -> Option<&FieldError> {
+        match &self.inner.kind {
+            Kind::FieldError(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Returns every failed attempt, if this error was caused by exhausting all retries.
+    pub fn retry_error(&self) -> Option<&RetryError> {
+        self.find_source()
+    }
+
+    /// Find the first error of type `E` in the chain of sources.
+    pub fn find_source<E: StdError + 'static>(&self) -> Option<&E> {
         let mut cause = self.source();
         while let Some(err) = cause {
-            if let Some(ref typed) = err.downcast_ref() {
+            if let Some(typed) = err.downcast_ref() {
                 return Some(typed);
             }
             cause = err.source();
@@ -88,16 +250,29 @@ impl Error {
         Error::new(Kind::ParsingError).with(cause)
     }
 
-    pub(super) fn new_retry_error<E: Into<Cause>>(cause: E) -> Self {
-        Error::new(Kind::RetryError).with(cause)
+    pub(super) fn new_retry_error(err: RetryError) -> Self {
+        Error::new(Kind::RetryError).with(err)
+    }
+
+    pub(super) fn new_timeout_error() -> Self {
+        Error::new(Kind::TimeoutError)
+    }
+
+    pub(super) fn new_reserved_header_error(name: &str) -> Self {
+        let message = format!("header {} is set by the client", name);
+        Error::new(Kind::InvalidHeaderName).with(CustomError::new(&message))
     }
 
     pub(super) fn new_connect_error(err: ConnectAPIError) -> Self {
         Error::new(Kind::ConnectAPIError(err))
     }
 
-    pub(super) fn new_internal_error() -> Self {
-        Error::new(Kind::InternalError)
+    pub(crate) fn new_validation_error(err: ValidationError) -> Self {
+        Error::new(Kind::ValidationError(err))
+    }
+
+    pub(crate) fn new_field_error(err: FieldError) -> Self {
+        Error::new(Kind::FieldError(err))
     }
 
     /// The error's standalone message, without the message from the source.
@@ -105,27 +280,106 @@ impl Error {
         self.description()
     }
 
+    /// Returns the classification of this error. Once every retry attempt is exhausted, this is
+    /// the classification of the last attempt's error.
+    pub fn kind(&self) -> ErrorKind {
+        match &self.inner.kind {
+            Kind::RetryError => match self.retry_error().and_then(|err| err.attempts.last()) {
+                Some(attempt) => attempt.error.kind(),
+                None => ErrorKind::Transport,
+            },
+            Kind::HyperError | Kind::NetworkError => ErrorKind::Transport,
+            Kind::TimeoutError => ErrorKind::Timeout,
+            Kind::HyperHttpError | Kind::InvalidHeaderName | Kind::InvalidHeaderValue => {
+                ErrorKind::InvalidRequest
+            }
+            Kind::RequestNotSuccessful(_) | Kind::ConnectAPIError(_) => ErrorKind::Api,
+            Kind::ParsingError | Kind::SerdeJsonError | Kind::Utf8Error => ErrorKind::Decode,
+            Kind::ValidationError(_) => ErrorKind::Validation,
+            Kind::FieldError(_) => ErrorKind::Field,
+            Kind::IoError => ErrorKind::Io,
+        }
+    }
+
+    /// Returns the HTTP status code returned by the Connect API, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match &self.inner.kind {
+            Kind::RequestNotSuccessful(err) => Some(err.status),
+            Kind::ConnectAPIError(err) => StatusCode::from_u16(err.status).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the Connect API responded with `404 Not Found`.
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Returns true if the Connect API responded with `401 Unauthorized`, e.g. for an invalid
+    /// bearer token.
+    pub fn is_unauthorized(&self) -> bool {
+        self.status() == Some(StatusCode::UNAUTHORIZED)
+    }
+
+    /// Returns true if the Connect API responded with `403 Forbidden`.
+    pub fn is_forbidden(&self) -> bool {
+        self.status() == Some(StatusCode::FORBIDDEN)
+    }
+
+    /// Returns true if the Connect API responded with `409 Conflict`.
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(StatusCode::CONFLICT)
+    }
+
+    /// Returns true if the Connect API responded with `429 Too Many Requests`.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Returns true if the call timed out.
+    pub fn is_timeout(&self) -> bool {
+        self.kind() == ErrorKind::Timeout
+    }
+
+    /// Returns true if the failure happened at the connection level.
+    pub fn is_transport(&self) -> bool {
+        self.kind() == ErrorKind::Transport
+    }
+
+    /// Returns true if repeating the same call later may succeed: transport failures, timeouts,
+    /// rate limiting and server errors.
+    pub fn is_retryable(&self) -> bool {
+        match self.kind() {
+            ErrorKind::Transport | ErrorKind::Timeout => true,
+            _ => self.status().is_some_and(|status| {
+                status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }),
+        }
+    }
+
     fn description(&self) -> String {
         match &self.inner.kind {
-            Kind::HyperError(_) => "this is a Hyper related error!".to_string(),
-            Kind::HyperHttpError(_) => "this is a Hyper HTTP related error!".to_string(),
-            Kind::InternalError => "internal error".to_string(),
+            Kind::HyperError => "this is a Hyper related error!".to_string(),
+            Kind::HyperHttpError => "this is a Hyper HTTP related error!".to_string(),
+            Kind::InvalidHeaderName => "invalid header name".to_string(),
             Kind::InvalidHeaderValue => "invalid header value".to_string(),
             Kind::NetworkError => "network error".to_string(),
-            Kind::NotImplementedError => "not implemented error".to_string(),
             Kind::ParsingError => "parsing error".to_string(),
             Kind::RetryError => "retry error".to_string(),
+            Kind::TimeoutError => "request timed out".to_string(),
             Kind::RequestNotSuccessful(err) => {
                 format!("client returned an unsuccessful HTTP status code: {}", err)
             }
-            Kind::SerdeJsonError(_) => "serde deserialization error".to_string(),
+            Kind::SerdeJsonError => "serde deserialization error".to_string(),
             Kind::Utf8Error => "parsing bytes experienced a UTF8 error".to_string(),
-            Kind::CustomError(err) => {
-                format!("Error: {}", err)
-            }
             Kind::ConnectAPIError(err) => {
                 format!("Connect API error: {}", err)
             }
+            Kind::ValidationError(err) => {
+                format!("validation error: {}", err)
+            }
+            Kind::FieldError(err) => format!("field error: {}", err),
+            Kind::IoError => "I/O error".to_string(),
         }
     }
 }
@@ -154,23 +408,53 @@ impl Display for RequestNotSuccessful {
     }
 }
 
-/// Wrapper type which contains Vault errors.
-#[derive(Debug)]
+/// Error returned by the Connect API, decoded from its `{"status": .., "message": ..}` body.
+#[derive(Debug, Deserialize)]
 pub struct ConnectAPIError {
     /// Error message from the API.
+    #[serde(default)]
     pub message: String,
     /// Status code returned by the HTTP call.
-    pub status: String,
+    #[serde(default)]
+    pub status: u16,
 }
 
 impl ConnectAPIError {
     /// Create a new unsuccessful request error.
-    pub fn new(status: String, message: &str) -> Self {
+    pub fn new(status: u16, message: &str) -> Self {
         Self {
-            status: status.to_string(),
+            status,
             message: message.to_string(),
         }
     }
+
+    /// Decode the error body of an unsuccessful response.
+    ///
+    /// Falls back to the HTTP status and the raw body when the body is not a Connect error
+    /// object, so this never fails.
+    pub fn from_response(status: StatusCode, body: &[u8]) -> Self {
+        match serde_json::from_slice::<ConnectAPIError>(body) {
+            Ok(mut err) => {
+                if err.status == 0 {
+                    err.status = status.as_u16();
+                }
+                if err.message.is_empty() {
+                    err.message = status.canonical_reason().unwrap_or_default().to_string();
+                }
+
+                err
+            }
+            Err(_) => {
+                let body = String::from_utf8_lossy(body);
+                let message = match body.trim() {
+                    "" => status.canonical_reason().unwrap_or_default(),
+                    body => body,
+                };
+
+                Self::new(status.as_u16(), message)
+            }
+        }
+    }
 }
 
 impl StdError for ConnectAPIError {}
@@ -181,6 +465,238 @@ impl Display for ConnectAPIError {
     }
 }
 
+/// A single failed attempt at sending a request.
+#[derive(Debug)]
+pub struct RetryAttempt {
+    /// The error which caused the attempt to fail.
+    pub error: Error,
+    /// How long the client waited before making the next attempt.
+    pub wait: Duration,
+    /// Date and time when the attempt failed.
+    pub failed_at: DateTime<Utc>,
+}
+
+impl RetryAttempt {
+    /// Create a new failed attempt, timestamped now.
+    pub fn new(error: Error, wait: Duration) -> Self {
+        Self {
+            error,
+            wait,
+            failed_at: Utc::now(),
+        }
+    }
+}
+
+/// Wrapper type which contains every failed attempt of a request, in order.
+#[derive(Debug)]
+pub struct RetryError {
+    /// The failed attempts.
+    pub attempts: Vec<RetryAttempt>,
+}
+
+impl RetryError {
+    /// Create a new retry error.
+    pub fn new(attempts: Vec<RetryAttempt>) -> Self {
+        Self { attempts }
+    }
+}
+
+impl StdError for RetryError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.attempts
+            .last()
+            .map(|attempt| &attempt.error as &(dyn StdError + 'static))
+    }
+}
+
+impl Display for RetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} attempts failed", self.attempts.len())?;
+        for (i, attempt) in self.attempts.iter().enumerate() {
+            write!(
+                f,
+                "; #{} at {} (waited {:?}): {}",
+                i + 1,
+                attempt.failed_at.to_rfc3339(),
+                attempt.wait,
+                attempt.error
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Machine-readable reason for a [`ValidationProblem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValidationCode {
+    /// The item has no title.
+    MissingTitle,
+    /// A field required by the item's category is missing.
+    MissingRequiredField,
+    /// Two fields in the same section share a label.
+    DuplicateFieldLabel,
+    /// A URL could not be parsed as an absolute URL.
+    InvalidUrl,
+    /// A value exceeds the maximum accepted length.
+    ValueTooLong,
+    /// A password generator recipe is outside Connect's limits.
+    InvalidRecipe,
+    /// A vault or item ID is not in Connect's 26-character format.
+    InvalidId,
+    /// A one-time password secret or `otpauth://` URI could not be parsed.
+    InvalidOtp,
+    /// A credit card number is malformed or fails the Luhn check.
+    InvalidCardNumber,
+    /// A `MONTH_YEAR` value is not a `YYYYMM` date.
+    InvalidMonthYear,
+    /// An `SSHKEY` value is not a private key in OpenSSH format.
+    InvalidSshKey,
+    /// A port is not a number between 0 and 65535.
+    InvalidPort,
+    /// No connection string can be rendered for the type of database.
+    UnsupportedDatabaseType,
+    /// An IBAN is malformed or fails its check digits.
+    InvalidIban,
+    /// A SWIFT (BIC) code is not 8 or 11 characters in the expected format.
+    InvalidSwift,
+    /// An item has URLs but not exactly one of them is primary.
+    InvalidPrimaryUrl,
+    /// An item being replaced or patched, or a field changed by a patch, has no ID.
+    MissingId,
+    /// Generating a key pair locally failed.
+    KeyGenerationFailed,
+}
+
+impl Display for ValidationCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Self::MissingTitle => "missing title",
+            Self::MissingRequiredField => "missing required field",
+            Self::DuplicateFieldLabel => "duplicate field label",
+            Self::InvalidUrl => "invalid URL",
+            Self::ValueTooLong => "value too long",
+            Self::InvalidRecipe => "invalid generator recipe",
+            Self::InvalidId => "invalid ID",
+            Self::InvalidOtp => "invalid one-time password",
+            Self::InvalidCardNumber => "invalid card number",
+            Self::InvalidMonthYear => "invalid month and year",
+            Self::InvalidSshKey => "invalid SSH key",
+            Self::InvalidPort => "invalid port",
+            Self::UnsupportedDatabaseType => "unsupported database type",
+            Self::InvalidIban => "invalid IBAN",
+            Self::InvalidSwift => "invalid SWIFT code",
+            Self::InvalidPrimaryUrl => "not exactly one primary URL",
+            Self::MissingId => "missing item ID",
+            Self::KeyGenerationFailed => "key generation failed",
+        };
+
+        f.write_str(value)
+    }
+}
+
+/// A single problem found while validating an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationProblem {
+    /// The field the problem relates to, e.g. `title`, `password` or a field's label.
+    pub field: String,
+    /// Machine-readable reason.
+    pub code: ValidationCode,
+}
+
+impl ValidationProblem {
+    /// Create a new problem.
+    pub fn new(field: &str, code: ValidationCode) -> Self {
+        Self {
+            field: field.to_string(),
+            code,
+        }
+    }
+}
+
+impl Display for ValidationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.code)
+    }
+}
+
+/// Wrapper type which contains every problem found while validating an item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The problems found, in the order they were detected.
+    pub problems: Vec<ValidationProblem>,
+}
+
+impl ValidationError {
+    /// Create a new validation error.
+    pub fn new(problems: Vec<ValidationProblem>) -> Self {
+        Self { problems }
+    }
+
+    /// Returns true if any problem has the given code.
+    pub fn has(&self, code: ValidationCode) -> bool {
+        self.problems.iter().any(|problem| problem.code == code)
+    }
+}
+
+impl StdError for ValidationError {}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problems: Vec<String> = self.problems.iter().map(|p| p.to_string()).collect();
+        f.write_str(&problems.join("; "))
+    }
+}
+
+/// Why a field lookup failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldErrorCode {
+    /// No field matches.
+    NotFound,
+    /// More than one field matches.
+    Ambiguous,
+    /// The field exists but has no value.
+    MissingValue,
+}
+
+/// A field lookup on an item which did not resolve to exactly one field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// What was looked up, e.g. `label "password"` or `purpose PASSWORD`.
+    pub selector: String,
+    /// Machine-readable reason.
+    pub code: FieldErrorCode,
+    /// The number of fields which matched.
+    pub matches: usize,
+}
+
+impl FieldError {
+    /// Create a new field error.
+    pub fn new(selector: &str, code: FieldErrorCode, matches: usize) -> Self {
+        Self {
+            selector: selector.to_string(),
+            code,
+            matches,
+        }
+    }
+}
+
+impl StdError for FieldError {}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            FieldErrorCode::NotFound => write!(f, "no field matches {}", self.selector),
+            FieldErrorCode::Ambiguous => {
+                write!(f, "{} fields match {}", self.matches, self.selector)
+            }
+            FieldErrorCode::MissingValue => write!(f, "field {} has no value", self.selector),
+        }
+    }
+}
+
 /// Wrapper type for custom errors.
 #[derive(Debug)]
 pub struct CustomError {
@@ -207,30 +723,35 @@ impl Display for CustomError {
 
 #[derive(Debug)]
 pub(super) enum Kind {
-    CustomError(CustomError),
-
     /// The failure was due to a Hyper error
-    HyperError(hyper::Error),
+    HyperError,
 
     /// The failure was due to a Hyper error
-    HyperHttpError(hyper::http::Error),
+    HyperHttpError,
 
-    InternalError,
+    InvalidHeaderName,
 
     InvalidHeaderValue,
 
     /// The failure was due to the network client not working properly.
     NetworkError,
 
-    NotImplementedError,
-
     ParsingError,
 
     RetryError,
 
+    /// The call did not complete within its deadline or attempt timeout.
+    TimeoutError,
+
     RequestNotSuccessful(RequestNotSuccessful),
 
-    SerdeJsonError(serde_json::Error),
+    SerdeJsonError,
+
+    ValidationError(ValidationError),
+
+    FieldError(FieldError),
+
+    IoError,
 
     Utf8Error,
 
@@ -240,14 +761,14 @@ pub(super) enum Kind {
 impl fmt::Display for Kind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            &Self::HyperError(_) => {
+            Self::HyperError => {
                 write!(f, "HyperError")
             }
-            &Self::HyperHttpError(_) => {
+            Self::HyperHttpError => {
                 write!(f, "HyperHttpError")
             }
-            Self::InternalError => {
-                write!(f, "InternalError")
+            Self::InvalidHeaderName => {
+                write!(f, "InvalidHeaderName")
             }
             Self::InvalidHeaderValue => {
                 write!(f, "InvalidHeaderValue")
@@ -255,29 +776,35 @@ impl fmt::Display for Kind {
             Self::NetworkError => {
                 write!(f, "NetworkError")
             }
-            Self::NotImplementedError => {
-                write!(f, "NotImplementedError")
-            }
             Self::ParsingError => {
                 write!(f, "ParsingError")
             }
             Self::RetryError => {
                 write!(f, "RetryError")
             }
-            &Self::RequestNotSuccessful(_) => {
+            Self::TimeoutError => {
+                write!(f, "TimeoutError")
+            }
+            Self::RequestNotSuccessful(_) => {
                 write!(f, "RequestNotSuccessful")
             }
-            &Self::SerdeJsonError(_) => {
+            Self::SerdeJsonError => {
                 write!(f, "SerdeJsonError")
             }
             Self::Utf8Error => {
                 write!(f, "Utf8Error")
             }
-            &Self::ConnectAPIError(_) => {
+            Self::ConnectAPIError(_) => {
                 write!(f, "ConnectAPIError")
             }
-            &Self::CustomError(_) => {
-                write!(f, "CustomError")
+            Self::ValidationError(_) => {
+                write!(f, "ValidationError")
+            }
+            Self::FieldError(_) => {
+                write!(f, "FieldError")
+            }
+            Self::IoError => {
+                write!(f, "IoError")
             }
         }
     }
@@ -285,13 +812,19 @@ impl fmt::Display for Kind {
 
 impl From<hyper::Error> for Error {
     fn from(err: hyper::Error) -> Self {
-        Error::new(Kind::HyperError(err))
+        Error::new(Kind::HyperError).with(err)
     }
 }
 
 impl From<hyper::http::Error> for Error {
     fn from(err: hyper::http::Error) -> Self {
-        Error::new(Kind::HyperHttpError(err))
+        Error::new(Kind::HyperHttpError).with(err)
+    }
+}
+
+impl From<InvalidHeaderName> for Error {
+    fn from(_err: InvalidHeaderName) -> Self {
+        Error::new(Kind::InvalidHeaderName)
     }
 }
 
@@ -313,49 +846,157 @@ impl From<Utf8Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::new(Kind::IoError).with(err)
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
-        Error::new(Kind::SerdeJsonError(err))
+        Error::new(Kind::SerdeJsonError).with(err)
     }
 }
 
-/// Defines an error from the 1Password Connect API
-#[derive(Debug)]
-pub struct OPError {
-    pub(super) status_code: Option<u16>,
-    pub(super) captures: Option<Vec<String>>,
-}
-
-/// Handle Connect API error response
-pub fn process_connect_error_response(err_message: String) -> Result<OPError, Error> {
-    let input_re = Regex::new(r#"(StatusCode):\s+(\d+)"#).unwrap();
-
-    // Execute the Regex
-    let captures = input_re.captures(&err_message).map(|captures| {
-        captures
-            .iter() // All the captured groups
-            .skip(1) // Skipping the complete match
-            .flat_map(|c| c) // Ignoring all empty optional matches
-            .map(|c| c.as_str()) // Grab the original strings
-            .collect::<Vec<_>>() // Create a vector
-    });
-
-    dbg!(&captures);
-
-    // Match against the captured values as a slice
-    let status_code: Option<u16> = match captures.as_ref().map(|c| c.as_slice()) {
-        Some(["StatusCode", x]) => {
-            let x = x.parse().expect("can't parse number");
-            Some(x)
-        }
-        _ => None,
-    };
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hyper::header::HeaderValue;
+
+    fn connect_error(status: u16) -> Error {
+        Error::new_connect_error(ConnectAPIError::new(status, "message"))
+    }
+
+    #[test]
+    fn constructors_map_to_kinds() {
+        let io = || std::io::Error::other("io");
+
+        assert_eq!(Error::new_network_error(io()).kind(), ErrorKind::Transport);
+        let retry_error = RetryError::new(vec![]);
+        assert_eq!(
+            Error::new_retry_error(retry_error).kind(),
+            ErrorKind::Transport
+        );
+        assert_eq!(Error::new_timeout_error().kind(), ErrorKind::Timeout);
+        assert_eq!(Error::new_parsing_error(io()).kind(), ErrorKind::Decode);
+        assert_eq!(connect_error(404).kind(), ErrorKind::Api);
+
+        let not_successful = RequestNotSuccessful::new(StatusCode::BAD_GATEWAY, String::new());
+        assert_eq!(Error::from(not_successful).kind(), ErrorKind::Api);
+
+        let invalid_header = HeaderValue::from_str("\n").unwrap_err();
+        assert_eq!(
+            Error::from(invalid_header).kind(),
+            ErrorKind::InvalidRequest
+        );
+
+        let serde_error = serde_json::from_str::<u8>("x").unwrap_err();
+        assert_eq!(Error::from(serde_error).kind(), ErrorKind::Decode);
+    }
+
+    #[test]
+    fn status_predicates() {
+        assert!(connect_error(404).is_not_found());
+        assert!(connect_error(401).is_unauthorized());
+        assert!(connect_error(403).is_forbidden());
+        assert!(connect_error(409).is_conflict());
+        assert!(connect_error(429).is_rate_limited());
+        assert_eq!(connect_error(400).status(), Some(StatusCode::BAD_REQUEST));
+        assert!(!connect_error(400).is_not_found());
+    }
+
+    #[test]
+    fn retryable() {
+        assert!(connect_error(429).is_retryable());
+        assert!(connect_error(503).is_retryable());
+        assert!(!connect_error(404).is_retryable());
+        assert!(Error::new_timeout_error().is_retryable());
+        assert!(Error::new_network_error(CustomError::new("reset")).is_transport());
+        assert!(!Error::new_parsing_error(CustomError::new("eof")).is_retryable());
+    }
+
+    #[test]
+    fn connect_error_body() {
+        let body = br#"{"status":401,"message":"Invalid bearer token"}"#;
+        let err = ConnectAPIError::from_response(StatusCode::UNAUTHORIZED, body);
+
+        assert_eq!(err.status, 401);
+        assert_eq!(err.message, "Invalid bearer token");
+    }
+
+    #[test]
+    fn connect_error_body_fallbacks() {
+        let err = ConnectAPIError::from_response(StatusCode::BAD_GATEWAY, b"<html>oops</html>");
+        assert_eq!(err.status, 502);
+        assert_eq!(err.message, "<html>oops</html>");
+
+        let err = ConnectAPIError::from_response(StatusCode::NOT_FOUND, b"");
+        assert_eq!(err.status, 404);
+        assert_eq!(err.message, "Not Found");
+
+        let err = ConnectAPIError::from_response(StatusCode::BAD_REQUEST, br#"{"status":"x"}"#);
+        assert_eq!(err.status, 400);
+
+        let err = ConnectAPIError::from_response(StatusCode::FORBIDDEN, br#"{"message":"no"}"#);
+        assert_eq!(err.status, 403);
+        assert_eq!(err.message, "no");
+
+        let err = ConnectAPIError::from_response(StatusCode::BAD_REQUEST, &[0xff, 0xfe]);
+        assert_eq!(err.status, 400);
+    }
+
+    #[test]
+    fn context_in_display() {
+        let err = connect_error(404)
+            .with_operation(
+                "items::get",
+                Method::GET,
+                "v1/vaults/{vault_id}/items/{item_id}",
+            )
+            .with_vault_id("vault")
+            .with_item_id("item")
+            .with_attempts(1)
+            .with_request_id(Some("req-1"));
+
+        assert_eq!(err.context().operation, Some("items::get"));
+        assert_eq!(err.context().item_id.as_deref(), Some("item"));
+        assert_eq!(
+            err.to_string(),
+            "Connect API error: StatusCode: 404, Message: message (operation: items::get, \
+             request: GET v1/vaults/{vault_id}/items/{item_id}, vault_id: vault, item_id: item, \
+             attempts: 1, request_id: req-1)"
+        );
+    }
 
-    let return_captures: Option<Vec<String>> =
-        captures.map(|b| b.into_iter().map(|c| c.to_owned()).collect::<Vec<_>>());
+    #[test]
+    fn no_context_in_display() {
+        let err = Error::new_timeout_error();
 
-    Ok(OPError {
-        status_code,
-        captures: return_captures,
-    })
+        assert!(err.context().is_empty());
+        assert_eq!(err.to_string(), "request timed out");
+    }
+
+    #[test]
+    fn retry_error_lists_attempts() {
+        let attempts = vec![
+            RetryAttempt::new(
+                Error::new_network_error(CustomError::new("dns")),
+                Duration::from_millis(100),
+            ),
+            RetryAttempt::new(Error::new_timeout_error(), Duration::ZERO),
+        ];
+        let err = Error::new_retry_error(RetryError::new(attempts));
+
+        let retry_error = err.retry_error().unwrap();
+        assert_eq!(retry_error.attempts.len(), 2);
+        assert!(retry_error.attempts[0].error.is_transport());
+        assert!(retry_error.attempts[1].error.is_timeout());
+        assert_eq!(err.kind(), ErrorKind::Timeout);
+        assert!(err
+            .to_string()
+            .starts_with("retry error: 2 attempts failed; #1 at "));
+        assert!(Error::new_parsing_error(CustomError::new("eof"))
+            .retry_error()
+            .is_none());
+    }
 }
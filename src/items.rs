@@ -1,187 +1,235 @@
 //! Vault items
 
-use crate::error::{ConnectAPIError, Error};
+use crate::error::Error;
 use crate::{
-    client::HTTPClient,
+    client::{CallOptions, HTTPClient, Response, DELETE, GET, PATCH, POST, PUT},
+    error::{ValidationCode, ValidationError, ValidationProblem},
     models::{
-        item::{FullItem, ItemBuilder, ItemData, LoginItem},
-        StatusWrapper,
+        item::{FullItem, ItemData, ItemPatch},
+        ItemId, VaultId,
     },
-    *,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 
 /// Get all items
-pub async fn all(
+pub async fn all(client: &impl HTTPClient, id: &VaultId) -> Result<Response<Vec<ItemData>>, Error> {
+    all_with_options(client, id, &CallOptions::default()).await
+}
+
+/// Get all items, applying the given [`CallOptions`]
+pub async fn all_with_options(
     client: &impl HTTPClient,
-    id: &str,
-) -> Result<(Vec<ItemData>, serde_json::Value), crate::error::Error> {
+    id: &VaultId,
+    options: &CallOptions,
+) -> Result<Response<Vec<ItemData>>, Error> {
     let params = vec![("", "")];
     let path = format!("v1/vaults/{}/items", id);
 
-    let result = match client
-        .send_request::<Vec<ItemData>>("GET", &path, &params, None)
+    client
+        .send_request::<Vec<ItemData>>("GET", &path, &params, None, options)
         .await
-    {
-        Ok(value) => value,
-        Err(err) => {
-            let op_error = crate::error::process_connect_error_response(err.to_string())?;
-
-            let message = "Invalid bearer token";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            return Err(Error::new_internal_error().with(err));
-        }
-    };
-
-    Ok(result)
+        .map_err(|err| {
+            err.with_operation("items::all", GET, "v1/vaults/{vault_id}/items")
+                .with_vault_id(id.as_str())
+        })
 }
 
 /// Get item details
 pub async fn get(
     client: &impl HTTPClient,
-    vault_id: &str,
-    item_id: &str,
-) -> Result<(FullItem, serde_json::Value), crate::error::Error> {
+    vault_id: &VaultId,
+    item_id: &ItemId,
+) -> Result<Response<FullItem>, Error> {
+    get_with_options(client, vault_id, item_id, &CallOptions::default()).await
+}
+
+/// Get item details, applying the given [`CallOptions`]
+pub async fn get_with_options(
+    client: &impl HTTPClient,
+    vault_id: &VaultId,
+    item_id: &ItemId,
+    options: &CallOptions,
+) -> Result<Response<FullItem>, Error> {
     let params = vec![("", "")];
     let path = format!("v1/vaults/{}/items/{}", vault_id, item_id);
 
     let body = None;
-    let result = match client
-        .send_request::<FullItem>("GET", &path, &params, body)
+    client
+        .send_request::<FullItem>("GET", &path, &params, body, options)
         .await
-    {
-        Ok(value) => value,
-        Err(err) => {
-            let op_error = crate::error::process_connect_error_response(err.to_string())?;
-
-            let message = "Invalid bearer token";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            return Err(Error::new_internal_error().with(err));
-        }
-    };
-
-    Ok(result)
+        .map_err(|err| {
+            err.with_operation("items::get", GET, "v1/vaults/{vault_id}/items/{item_id}")
+                .with_vault_id(vault_id.as_str())
+                .with_item_id(item_id.as_str())
+        })
 }
 
 /// Add an item
-pub async fn add(
+pub async fn add(client: &impl HTTPClient, item: FullItem) -> Result<Response<ItemData>, Error> {
+    add_with_options(client, item, &CallOptions::default()).await
+}
+
+/// Add an item, applying the given [`CallOptions`]
+pub async fn add_with_options(
     client: &impl HTTPClient,
     item: FullItem,
-) -> Result<(ItemData, serde_json::Value), crate::error::Error> {
+    options: &CallOptions,
+) -> Result<Response<ItemData>, Error> {
     let id = &item.vault.id;
 
     let params = vec![("", "")];
     let path = format!("v1/vaults/{}/items", id);
 
     let body = Some(serde_json::to_string(&item)?);
-    let result = match client
-        .send_request::<ItemData>("POST", &path, &params, body)
+    client
+        .send_request::<ItemData>("POST", &path, &params, body, options)
         .await
-    {
-        Ok(value) => value,
-        Err(err) => {
-            let op_error = crate::error::process_connect_error_response(err.to_string())?;
-
-            let message = "Invalid bearer token";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            return Err(Error::new_internal_error().with(err));
-        }
-    };
+        .map_err(|err| {
+            err.with_operation("items::add", POST, "v1/vaults/{vault_id}/items")
+                .with_vault_id(id.as_str())
+        })
+}
 
-    Ok(result)
+/// Replace an item with an edited copy, e.g. from [`ItemEditor::build`]
+///
+/// [`ItemEditor::build`]: crate::models::item::ItemEditor::build
+pub async fn replace(
+    client: &impl HTTPClient,
+    item: FullItem,
+) -> Result<Response<FullItem>, Error> {
+    replace_with_options(client, item, &CallOptions::default()).await
+}
+
+/// Replace an item with an edited copy, applying the given [`CallOptions`]
+pub async fn replace_with_options(
+    client: &impl HTTPClient,
+    item: FullItem,
+    options: &CallOptions,
+) -> Result<Response<FullItem>, Error> {
+    let vault_id = &item.vault.id;
+    let item_id = item.id.as_ref().ok_or_else(|| {
+        Error::new_validation_error(ValidationError::new(vec![ValidationProblem::new(
+            "id",
+            ValidationCode::MissingId,
+        )]))
+    })?;
+
+    let params = vec![("", "")];
+    let path = format!("v1/vaults/{}/items/{}", vault_id, item_id);
+
+    let body = Some(serde_json::to_string(&item)?);
+    client
+        .send_request::<FullItem>("PUT", &path, &params, body, options)
+        .await
+        .map_err(|err| {
+            err.with_operation(
+                "items::replace",
+                PUT,
+                "v1/vaults/{vault_id}/items/{item_id}",
+            )
+            .with_vault_id(vault_id.as_str())
+            .with_item_id(item_id.as_str())
+        })
+}
+
+/// Apply the changes of an [`ItemPatch`], from [`ItemEditor::patch`]
+///
+/// [`ItemEditor::patch`]: crate::models::item::ItemEditor::patch
+pub async fn patch(
+    client: &impl HTTPClient,
+    patch: ItemPatch,
+) -> Result<Response<FullItem>, Error> {
+    patch_with_options(client, patch, &CallOptions::default()).await
+}
+
+/// Apply the changes of an [`ItemPatch`], applying the given [`CallOptions`]
+pub async fn patch_with_options(
+    client: &impl HTTPClient,
+    patch: ItemPatch,
+    options: &CallOptions,
+) -> Result<Response<FullItem>, Error> {
+    let ItemPatch {
+        vault_id,
+        item_id,
+        operations,
+    } = patch;
+
+    let params = vec![("", "")];
+    let path = format!("v1/vaults/{}/items/{}", vault_id, item_id);
+
+    let body = Some(serde_json::to_string(&operations)?);
+    client
+        .send_request::<FullItem>("PATCH", &path, &params, body, options)
+        .await
+        .map_err(|err| {
+            err.with_operation(
+                "items::patch",
+                PATCH,
+                "v1/vaults/{vault_id}/items/{item_id}",
+            )
+            .with_vault_id(vault_id.as_str())
+            .with_item_id(item_id.as_str())
+        })
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
 struct DeleteReturnType {}
 
 /// Delete an item
-pub async fn remove(
+pub async fn remove(client: &impl HTTPClient, id: &VaultId, item_id: &ItemId) -> Result<(), Error> {
+    remove_with_options(client, id, item_id, &CallOptions::default()).await
+}
+
+/// Delete an item, applying the given [`CallOptions`]
+pub async fn remove_with_options(
     client: &impl HTTPClient,
-    id: &str,
-    item_id: &str,
-) -> Result<(), crate::error::Error> {
+    id: &VaultId,
+    item_id: &ItemId,
+    options: &CallOptions,
+) -> Result<(), Error> {
     let params = vec![("", "")];
     let path = format!("v1/vaults/{}/items/{}", id, item_id);
-    dbg!(&path);
 
     let body = None;
-    let _result = match client
-        .send_request::<DeleteReturnType>("DELETE", &path, &params, body)
+    client
+        .send_request::<DeleteReturnType>("DELETE", &path, &params, body, options)
         .await
-    {
-        Ok(value) => value,
-        Err(err) => {
-            let op_error = crate::error::process_connect_error_response(err.to_string())?;
-
-            let message = "Invalid bearer token";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            return Err(Error::new_internal_error().with(err));
-        }
-    };
+        .map_err(|err| {
+            err.with_operation(
+                "items::remove",
+                DELETE,
+                "v1/vaults/{vault_id}/items/{item_id}",
+            )
+            .with_vault_id(id.as_str())
+            .with_item_id(item_id.as_str())
+        })?;
 
     Ok(())
 }
 
+#[cfg(test)]
 const SLEEP_DELAY: u64 = 4; // seconds
 
 #[cfg(test)]
 mod default {
     use super::SLEEP_DELAY;
-    use crate::get_test_client;
+    use crate::{get_test_client, get_test_vault_id};
     use tokio::test;
 
     use crate::{
         items,
-        models::item::{DefaultItem, FullItem, ItemBuilder, ItemCategory},
+        models::item::{FullItem, ItemBuilder, ItemCategory},
     };
 
     #[test]
     async fn all() {
         let client = get_test_client();
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
 
-        let (items, _) = items::all(&client, &test_vault_id).await.unwrap();
+        let items = items::all(&client, &test_vault_id)
+            .await
+            .unwrap()
+            .into_value();
         dbg!(&items);
 
         assert!(items.is_empty());
@@ -189,14 +237,14 @@ mod default {
 
     #[test]
     async fn add_item() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::ApiCredential)
+        let item: FullItem = ItemBuilder::custom(&test_vault_id, ItemCategory::ApiCredential)
+            .title("Test item")
             .build()
             .unwrap();
-        let (new_item, _) = items::add(&client, item).await.unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
         dbg!(&new_item);
 
         assert_ne!(new_item.id, "foo");
@@ -212,27 +260,26 @@ mod default {
 #[cfg(test)]
 mod login_item {
     use super::SLEEP_DELAY;
-    use crate::get_test_client;
+    use crate::{get_test_client, get_test_vault_id};
     use tokio::test;
 
     use crate::{
         items,
-        models::item::{FullItem, ItemBuilder, ItemCategory, LoginItem},
+        models::item::{FullItem, ItemBuilder},
     };
 
     #[test]
     async fn add_login_item() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::Login)
+        let item: FullItem = ItemBuilder::login(&test_vault_id)
             .title("Test login item")
             .username("Bob")
             .password("")
             .build()
             .unwrap();
-        let (new_item, _) = items::add(&client, item).await.unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
         dbg!(&new_item);
 
         assert_ne!(new_item.id, "foo");
@@ -246,18 +293,17 @@ mod login_item {
 
     #[test]
     async fn add_login_item_with_otp() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::Login)
+        let item: FullItem = ItemBuilder::login(&test_vault_id)
             .title("Test login item")
             .username("Bob")
             .password("")
-            .add_otp("replaceme")
+            .add_otp("otpauth://totp/Test:Bob?secret=JBSWY3DPEHPK3PXP&issuer=Test")
             .build()
             .unwrap();
-        let (new_item, _) = items::add(&client, item).await.unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
         dbg!(&new_item);
 
         assert_ne!(new_item.id, "foo");
@@ -272,31 +318,72 @@ mod login_item {
     #[should_panic]
     #[test]
     async fn add_login_item_requires_title() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::Login)
+        let item: FullItem = ItemBuilder::login(&test_vault_id)
+            .title("")
             .username("Bob")
             .password("")
             .build()
             .unwrap();
-        let (_new_item, _) = items::add(&client, item).await.unwrap();
+        let _new_item = items::add(&client, item).await.unwrap().into_value();
+    }
+
+    #[test]
+    async fn edit_login_item() {
+        let test_vault_id = get_test_vault_id();
+        let client = get_test_client();
+
+        let item: FullItem = ItemBuilder::login(&test_vault_id)
+            .title("Test login item, will be edited")
+            .username("Bob")
+            .password("hunter2")
+            .build()
+            .unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
+
+        tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY, 0)).await;
+
+        let item = items::get(&client, &test_vault_id, &new_item.id)
+            .await
+            .unwrap()
+            .into_value();
+        let patch = ItemBuilder::edit(item)
+            .set("password", "correct horse")
+            .patch()
+            .unwrap();
+        let item = items::patch(&client, patch).await.unwrap().into_value();
+        assert_eq!(item.password().unwrap().expose(), "correct horse");
+
+        let item = ItemBuilder::edit(item)
+            .title("Test login item, edited")
+            .rename("username", "login")
+            .build()
+            .unwrap();
+        let item = items::replace(&client, item).await.unwrap().into_value();
+        assert_eq!(item.title, "Test login item, edited");
+        assert!(item.field_by_label("login").is_ok());
+
+        tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY, 0)).await;
+
+        items::remove(&client, &test_vault_id, &new_item.id)
+            .await
+            .unwrap();
     }
 
     #[test]
     async fn remove_login_item() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::Login)
+        let item: FullItem = ItemBuilder::login(&test_vault_id)
             .title("Test login item, will be removed")
             .username("Bob")
             .password("")
             .build()
             .unwrap();
-        let (new_item, _) = items::add(&client, item).await.unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
         dbg!(&new_item);
 
         tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY + 2, 0)).await;
@@ -307,7 +394,10 @@ mod login_item {
 
         tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY, 0)).await;
 
-        let (items, _) = items::all(&client, &test_vault_id).await.unwrap();
+        let items = items::all(&client, &test_vault_id)
+            .await
+            .unwrap()
+            .into_value();
         assert!(items.is_empty());
     }
 }
@@ -315,44 +405,43 @@ mod login_item {
 #[cfg(test)]
 mod api_credential_item {
     use super::SLEEP_DELAY;
-    use crate::get_test_client;
+    use crate::{get_test_client, get_test_vault_id};
     use tokio::test;
 
     use crate::{
         items,
-        models::item::{ApiCredentialItem, FullItem, ItemBuilder, ItemCategory},
+        models::item::{FieldType, FullItem, ItemBuilder},
+        secret::SecretString,
     };
 
     #[test]
     async fn get_item() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::ApiCredential)
-            .api_key("lawyer-rottenborn", "Dell XYZ")
+        let item: FullItem = ItemBuilder::api_credential(&test_vault_id)
+            .title("Dell XYZ")
+            .api_key("lawyer-rottenborn")
             .build()
             .unwrap();
-        let (new_item, _) = items::add(&client, item).await.unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
         assert_eq!(new_item.title, "Dell XYZ");
 
         tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY, 0)).await;
 
-        let (item, _) = items::get(&client, &test_vault_id, &new_item.id)
+        let item = items::get(&client, &test_vault_id, &new_item.id)
             .await
-            .unwrap();
-        let fields: Vec<_> = item
-            .fields
-            .into_iter()
-            .filter(|r| r.value.is_some())
-            .collect();
-        assert_eq!(fields.len(), 1);
-        dbg!(&fields);
-
-        let default_value = "".to_string();
-        let api_value = fields[0].value.as_ref().unwrap_or(&default_value);
-        let field_type = fields[0].r#type.as_ref().unwrap_or(&default_value);
-        assert_eq!(field_type, "CONCEALED");
+            .unwrap()
+            .into_value();
+        let credential = item.field_by_label("credential").unwrap();
+        dbg!(&credential);
+
+        let api_value = credential
+            .value
+            .as_ref()
+            .map(SecretString::expose)
+            .unwrap_or_default();
+        assert_eq!(credential.r#type, Some(FieldType::Concealed));
         assert_eq!(api_value, "lawyer-rottenborn");
 
         // Just as a clean up measure, we remove the item created in the this example
@@ -365,15 +454,15 @@ mod api_credential_item {
 
     #[test]
     async fn add_api_credential_item() {
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
         let client = get_test_client();
 
-        let item: FullItem = ItemBuilder::new(&test_vault_id, ItemCategory::ApiCredential)
-            .api_key("", "Dell XYZ")
+        let item: FullItem = ItemBuilder::api_credential(&test_vault_id)
+            .title("Dell XYZ")
+            .api_key("")
             .build()
             .unwrap();
-        let (new_item, _) = items::add(&client, item).await.unwrap();
+        let new_item = items::add(&client, item).await.unwrap().into_value();
         dbg!(&new_item);
 
         assert_ne!(new_item.id, "foo");
@@ -1,7 +1,7 @@
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 #![forbid(unsafe_code)]
-#![deny(private_in_public, unstable_features)]
+#![deny(unstable_features)]
 #![warn(rust_2018_idioms, future_incompatible, nonstandard_style)]
 
 //! connect-1password is a Rust SDK for 1Password Connect.
@@ -15,15 +15,15 @@
 //!
 //! ## Create a Login item
 //!
-//! To create a Login item, make sure to use the Trait [`LoginItem`](models::item::LoginItem), so as to be able to call
-//! respective methods (enforced by the interface) on [`ItemBuilder`](models::item::ItemBuilder).
+//! To create a Login item, start from [`ItemBuilder::login`](models::item::ItemBuilder::login); the
+//! builder only has a `build` method once a title is given.
 //!
 //! ```
 //! use connect_1password::{
 //!     error::Error,
 //!     client::{Client, HTTPClient},
 //!     models::{
-//!         item::{LoginItem, FullItem, ItemBuilder, ItemCategory},
+//!         item::{FullItem, ItemBuilder},
 //!     },
 //!     vaults,
 //!     items,
@@ -35,17 +35,17 @@
 //! async fn main() -> Result<(), Error> {
 //!     let client = Client::default();
 //!
-//!     let (vaults, _) = vaults::all(&client).await?;
+//!     let vaults = vaults::all(&client).await?.into_value();
 //!     assert!(!vaults.is_empty());
 //!
-//!     let item: FullItem = ItemBuilder::new(&vaults[0].id, ItemCategory::Login)
+//!     let item: FullItem = ItemBuilder::login(&vaults[0].id)
 //!         .title("Secure server login")
 //!         .username("Bob")
 //!         .password("")
 //!         .build()
 //!         .unwrap();
 //!
-//!     let (new_item, _) = items::add(&client, item).await?;
+//!     let new_item = items::add(&client, item).await?.into_value();
 //!     assert_eq!(new_item.title, "Secure server login");
 //!
 //!     // Just as a clean up measure, we remove the item created in the this example
@@ -71,8 +71,9 @@
 //!     error::Error,
 //!     client::{Client, HTTPClient},
 //!     models::{
-//!         item::{ApiCredentialItem, FullItem, ItemBuilder, ItemCategory, FieldObject},
+//!         item::{FullItem, ItemBuilder, FieldType},
 //!     },
+//!     secret::SecretString,
 //!     vaults,
 //!     items,
 //! };
@@ -83,29 +84,26 @@
 //! async fn main() -> Result<(), Error> {
 //!     let client = Client::default();
 //!
-//!     let (vaults, _) = vaults::all(&client).await?;
+//!     let vaults = vaults::all(&client).await?.into_value();
 //!     assert!(!vaults.is_empty());
 //!
-//!     let item: FullItem = ItemBuilder::new(&vaults[0].id, ItemCategory::ApiCredential)
-//!         .api_key("", "Dell XYZ")
+//!     let item: FullItem = ItemBuilder::api_credential(&vaults[0].id)
+//!         .title("Dell XYZ")
+//!         .api_key("")
 //!         .build()
 //!         .unwrap();
 //!
 //!     let client = Client::default();
-//!     let (new_item, _) = items::add(&client, item).await?;
+//!     let new_item = items::add(&client, item).await?.into_value();
 //!     assert_eq!(new_item.title, "Dell XYZ");
 //!
 //!     tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY, 0)).await;
 //!
-//!     let (item, _) = items::get(&client, &vaults[0].id, &new_item.id).await?;
-//!     let fields: Vec<_> = item.fields.into_iter().filter(|r| r.value.is_some()).collect();
-//!     assert_eq!(fields.len(), 1);
-//!     dbg!(&fields);
+//!     let item = items::get(&client, &vaults[0].id, &new_item.id).await?.into_value();
+//!     let credential = item.field_by_label("credential")?;
 //!
-//!     let default_value = "".to_string();
-//!     let api_value = fields[0].value.as_ref().unwrap_or(&default_value);
-//!     let field_type = fields[0].r#type.as_ref().unwrap_or(&default_value);
-//!     assert_eq!(field_type, "CONCEALED");
+//!     let api_value = credential.value.as_ref().map(SecretString::expose).unwrap_or_default();
+//!     assert_eq!(credential.r#type, Some(FieldType::Concealed));
 //!     assert!(!api_value.is_empty());
 //!
 //!     // Just as a clean up measure, we remove the item created in the this example
@@ -125,8 +123,9 @@
 //!     error::Error,
 //!     client::{Client, HTTPClient},
 //!     models::{
-//!         item::{ApiCredentialItem, FullItem, ItemBuilder, ItemCategory, FieldObject},
+//!         item::{FullItem, ItemBuilder, FieldType},
 //!     },
+//!     secret::SecretString,
 //!     vaults,
 //!     items,
 //! };
@@ -137,29 +136,26 @@
 //! async fn main() -> Result<(), Error> {
 //!     let client = Client::default();
 //!
-//!     let (vaults, _) = vaults::all(&client).await?;
+//!     let vaults = vaults::all(&client).await?.into_value();
 //!     assert!(!vaults.is_empty());
 //!
-//!     let item: FullItem = ItemBuilder::new(&vaults[0].id, ItemCategory::ApiCredential)
-//!         .api_key("smelly-socks", "Dell XYZ")
+//!     let item: FullItem = ItemBuilder::api_credential(&vaults[0].id)
+//!         .title("Dell XYZ")
+//!         .api_key("smelly-socks")
 //!         .build()
 //!         .unwrap();
 //!
-//!     let (new_item, _) = items::add(&client, item).await?;
+//!     let new_item = items::add(&client, item).await?.into_value();
 //!     assert_eq!(new_item.title, "Dell XYZ");
 //!
 //!     tokio::time::sleep(std::time::Duration::new(SLEEP_DELAY, 0)).await;
 //!
 //!     let client = Client::default();
-//!     let (item, _) = items::get(&client, &vaults[0].id, &new_item.id).await?;
-//!     let fields: Vec<_> = item.fields.into_iter().filter(|r| r.value.is_some()).collect();
-//!     assert_eq!(fields.len(), 1);
-//!     dbg!(&fields);
-//!
-//!     let default_value = "".to_string();
-//!     let api_value = fields[0].value.as_ref().unwrap_or(&default_value);
-//!     let field_type = fields[0].r#type.as_ref().unwrap_or(&default_value);
-//!     assert_eq!(field_type, "CONCEALED");
+//!     let item = items::get(&client, &vaults[0].id, &new_item.id).await?.into_value();
+//!     let credential = item.field_by_label("credential")?;
+//!
+//!     let api_value = credential.value.as_ref().map(SecretString::expose).unwrap_or_default();
+//!     assert_eq!(credential.r#type, Some(FieldType::Concealed));
 //!     assert_eq!(api_value, "smelly-socks");
 //!
 //!     // Just as a clean up measure, we remove the item created in the this example
@@ -176,9 +172,19 @@ pub mod client;
 pub mod error;
 pub mod items;
 pub mod models;
+pub mod otp;
+pub mod secret;
 pub mod vaults;
 
 #[cfg(test)]
 fn get_test_client() -> client::Client {
     client::Client::default()
 }
+
+#[cfg(test)]
+fn get_test_vault_id() -> models::VaultId {
+    std::env::var("OP_TESTING_VAULT_ID")
+        .expect("1Password Vault ID for testing")
+        .parse()
+        .expect("valid 1Password Vault ID")
+}
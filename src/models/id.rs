@@ -0,0 +1,151 @@
+use crate::error::{Error, ValidationCode, ValidationError, ValidationProblem};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Length of the IDs Connect assigns to vaults and items.
+pub const ID_LENGTH: usize = 26;
+
+fn is_valid_id(value: &str) -> bool {
+    value.len() == ID_LENGTH
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+}
+
+macro_rules! connect_id {
+    ($(#[$meta:meta])* $name:ident, $field:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            /// Returns the ID as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                if is_valid_id(&value) {
+                    Ok(Self(value))
+                } else {
+                    Err(Error::new_validation_error(ValidationError::new(vec![
+                        ValidationProblem::new($field, ValidationCode::InvalidId),
+                    ])))
+                }
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = Error;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                Self::try_from(value.to_string())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Self::try_from(value)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+connect_id!(
+    /// The ID of a vault: 26 lowercase letters and digits.
+    ///
+    /// ```
+    /// use connect_1password::models::VaultId;
+    ///
+    /// let id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+    /// assert_eq!(id.as_str(), "ytrfte14kw1uex5txn3pnaoerm");
+    /// assert!("".parse::<VaultId>().is_err());
+    /// ```
+    VaultId,
+    "vault_id"
+);
+
+connect_id!(
+    /// The ID of an item: 26 lowercase letters and digits.
+    ItemId,
+    "item_id"
+);
+
+#[cfg(test)]
+mod test {
+    use super::{ItemId, VaultId};
+    use crate::error::ValidationCode;
+
+    #[test]
+    fn valid_ids() {
+        let id = VaultId::try_from("ytrfte14kw1uex5txn3pnaoerm").unwrap();
+        assert_eq!(id.to_string(), "ytrfte14kw1uex5txn3pnaoerm");
+        assert!(ItemId::try_from("2fcbqwe9ndg175zg2dzwftvkpa".to_string()).is_ok());
+    }
+
+    #[test]
+    fn invalid_ids() {
+        for value in [
+            "",
+            "foo",
+            "YTRFTE14KW1UEX5TXN3PNAOERM",
+            "ytrfte14kw1uex5txn3pnaoer-",
+            "ytrfte14kw1uex5txn3pnaoerma",
+        ] {
+            let err = VaultId::try_from(value).unwrap_err();
+            let validation = err.validation_error().unwrap();
+            assert!(validation.has(ValidationCode::InvalidId), "{}", value);
+            assert_eq!(validation.problems[0].field, "vault_id");
+        }
+
+        let err = "x".parse::<ItemId>().unwrap_err();
+        assert_eq!(err.validation_error().unwrap().problems[0].field, "item_id");
+    }
+
+    #[test]
+    fn serde() {
+        let id: ItemId = serde_json::from_str(r#""2fcbqwe9ndg175zg2dzwftvkpa""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&id).unwrap(),
+            r#""2fcbqwe9ndg175zg2dzwftvkpa""#
+        );
+        assert!(serde_json::from_str::<ItemId>(r#""""#).is_err());
+    }
+}
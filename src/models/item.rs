@@ -1,96 +1,500 @@
-use std::ascii::AsciiExt;
-
-use crate::error::{CustomError, Error};
+use super::{ItemId, VaultId};
+use crate::{
+    error::{
+        Error, FieldError, FieldErrorCode, ValidationCode, ValidationError, ValidationProblem,
+    },
+    otp::Totp,
+    secret::{SecretString, EMPTY},
+};
 use chrono::{DateTime, Utc};
-use hyper::StatusCode;
-use log::debug;
-use regex::Regex;
+use marker::{
+    ApiCredential, BankAccount, Category, CreditCard, Custom, Database, Identity, Login,
+    SecureNote, Server, SshKey, Titled, Untitled, Untyped,
+};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, marker::PhantomData};
+use url::Url;
 use uuid::Uuid;
 
+mod bank_account;
+mod credit_card;
+mod database;
+mod editor;
+mod identity;
+pub mod marker;
+mod section;
+mod secure_note;
+mod server;
+mod ssh;
+
+pub use credit_card::CardType;
+pub use database::DatabaseType;
+pub use editor::{ItemEditor, ItemPatch, PatchOp, PatchOperation};
+pub use identity::Address;
+pub use section::SectionBuilder;
+pub use server::{AdminConsole, HostingProvider};
+
+/// Longest field value accepted by the builder.
+pub const MAX_VALUE_LENGTH: usize = 65_536;
+
 /// Defines an Item Object
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct ItemData {
-    /// The UUID of the item.
-    pub id: String,
+    /// The ID of the item.
+    pub id: ItemId,
     /// The title of the item.
     pub title: String,
     /// An object containing an id property whose value is the UUID of the vault the item is in.
     pub vault: VaultID,
     /// The category of the item.
-    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<ItemCategory>,
     /// Vector of URL objects containing URLs for the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub urls: Option<Vec<UrlObject>>,
     /// Whether the item is marked as a favourite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub favorite: Option<bool>,
     /// A vector of strings of the tags assigned to the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+    /// The version of the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
     /// The state of the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
-    /// Date and time when the vault was created.
+    /// Date and time when the item was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
-    /// Date and time when the vault or its contents were last changed.
+    /// Date and time when the item was last changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    /// The ID of the user who last changed the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_edited_by: Option<String>,
 }
 
 /// Defines the Vault UUID via a record struct
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
 pub struct VaultID {
-    /// The UUID of the vault.
-    pub id: String,
+    /// The ID of the vault.
+    pub id: VaultId,
 }
 
 /// Defines a URL Object
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Clone)]
 pub struct UrlObject {
-    /// The address.
+    /// The address, sent to Connect as `href`.
+    #[serde(rename = "href", alias = "url")]
     pub url: String,
     /// Whether this is the primary URL for the item.
+    #[serde(default)]
     pub primary: bool,
+    /// Some optional text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
 }
 
 /// This is a Field Object
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct FieldObject {
+    /// The ID of the field, unique within the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     /// An object containing the UUID of a section in the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub section: Option<SectionID>,
     /// Use `purpose` for the username, password, and notes fields.
-    pub purpose: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<FieldPurpose>,
     /// Use `type' for all other fields
-    pub r#type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<FieldType>,
     /// The value to save for the field. You can specify a `generate` field instead of `value` to create a password or other random information for the value.
-    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<SecretString>,
     /// Generate a password and save in the value for the field. By default, the password is a 32-characters long, made up of letters, numbers, and symbols. To customize the password, include a `recipe` field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub generate: Option<bool>,
-    // FIXME the GeneratorRecipe needs to be added
-    // pub recipe
+    /// The recipe used to generate the value, when `generate` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipe: Option<GeneratorRecipe>,
     /// Some optional text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// The entropy of the value, in bits. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<f64>,
+    /// The current one-time password code for `OTP` fields. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub totp: Option<SecretString>,
+    /// The secret reference to the field, e.g. `op://vault/item/field`. Set by Connect on
+    /// fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Strength and history of a password. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_details: Option<PasswordDetails>,
+}
+
+/// Details about a password field, as reported by Connect.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordDetails {
+    /// The entropy of the password, in bits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<f64>,
+    /// Whether the password was generated by 1Password.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated: Option<bool>,
+    /// The strength of the password.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strength: Option<PasswordStrength>,
+    /// Previous values of the password, most recent first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<SecretString>>,
+}
+
+/// Strength of a password, as rated by 1Password.
+///
+/// Ratings unknown to this version of the SDK are kept as [`PasswordStrength::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum PasswordStrength {
+    /// Terrible
+    Terrible,
+    /// Weak
+    Weak,
+    /// Fair
+    Fair,
+    /// Good
+    Good,
+    /// Very good
+    VeryGood,
+    /// Excellent
+    Excellent,
+    /// Fantastic
+    Fantastic,
+    /// A rating not known to this SDK, holding the raw value
+    Unknown(String),
+}
+
+impl PasswordStrength {
+    /// Returns the value used by the Connect API, e.g. `VERY_GOOD`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Terrible => "TERRIBLE",
+            Self::Weak => "WEAK",
+            Self::Fair => "FAIR",
+            Self::Good => "GOOD",
+            Self::VeryGood => "VERY_GOOD",
+            Self::Excellent => "EXCELLENT",
+            Self::Fantastic => "FANTASTIC",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<&str> for PasswordStrength {
+    fn from(value: &str) -> Self {
+        match value {
+            "TERRIBLE" => Self::Terrible,
+            "WEAK" => Self::Weak,
+            "FAIR" => Self::Fair,
+            "GOOD" => Self::Good,
+            "VERY_GOOD" => Self::VeryGood,
+            "EXCELLENT" => Self::Excellent,
+            "FANTASTIC" => Self::Fantastic,
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for PasswordStrength {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<PasswordStrength> for String {
+    fn from(strength: PasswordStrength) -> Self {
+        strength.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for PasswordStrength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Used to specify type of a Field Object
-#[derive(Debug)]
+///
+/// Types unknown to this version of the SDK are kept as [`FieldType::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
 pub enum FieldType {
+    /// Plain text
+    String,
+    /// Email address
+    Email,
     /// Item value will be concealed
     Concealed,
+    /// Web address
+    Url,
+    /// One-time password secret, or `otpauth://` URI
+    Otp,
+    /// Date, as `YYYY-MM-DD`
+    Date,
+    /// Month and year, as `YYYYMM`
+    MonthYear,
+    /// Choice from a fixed list
+    Menu,
+    /// Phone number
+    Phone,
+    /// Postal address
+    Address,
+    /// Reference to another item
+    Reference,
+    /// SSH key
+    SshKey,
+    /// Credit card brand
+    CreditCardType,
+    /// Credit card number
+    CreditCardNumber,
+    /// A type not known to this SDK, holding the raw value
+    Unknown(String),
 }
 
-impl Into<String> for FieldType {
-    fn into(self) -> String {
-        let value = match self {
+impl FieldType {
+    /// Returns the value used by the Connect API, e.g. `CONCEALED`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::String => "STRING",
+            Self::Email => "EMAIL",
             Self::Concealed => "CONCEALED",
-        };
+            Self::Url => "URL",
+            Self::Otp => "OTP",
+            Self::Date => "DATE",
+            Self::MonthYear => "MONTH_YEAR",
+            Self::Menu => "MENU",
+            Self::Phone => "PHONE",
+            Self::Address => "ADDRESS",
+            Self::Reference => "REFERENCE",
+            Self::SshKey => "SSHKEY",
+            Self::CreditCardType => "CREDIT_CARD_TYPE",
+            Self::CreditCardNumber => "CREDIT_CARD_NUMBER",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<&str> for FieldType {
+    fn from(value: &str) -> Self {
+        match value {
+            "STRING" => Self::String,
+            "EMAIL" => Self::Email,
+            "CONCEALED" => Self::Concealed,
+            "URL" => Self::Url,
+            "OTP" => Self::Otp,
+            "DATE" => Self::Date,
+            "MONTH_YEAR" => Self::MonthYear,
+            "MENU" => Self::Menu,
+            "PHONE" => Self::Phone,
+            "ADDRESS" => Self::Address,
+            "REFERENCE" => Self::Reference,
+            "SSHKEY" => Self::SshKey,
+            "CREDIT_CARD_TYPE" => Self::CreditCardType,
+            "CREDIT_CARD_NUMBER" => Self::CreditCardNumber,
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
 
-        value.to_string()
+impl From<String> for FieldType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<FieldType> for String {
+    fn from(field_type: FieldType) -> Self {
+        field_type.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Used to specify the purpose of a Field Object
+///
+/// Purposes unknown to this version of the SDK are kept as [`FieldPurpose::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum FieldPurpose {
+    /// The item's username
+    Username,
+    /// The item's password
+    Password,
+    /// The item's notes
+    Notes,
+    /// A purpose not known to this SDK, holding the raw value
+    Unknown(String),
+}
+
+impl FieldPurpose {
+    /// Returns the value used by the Connect API, e.g. `USERNAME`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Username => "USERNAME",
+            Self::Password => "PASSWORD",
+            Self::Notes => "NOTES",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<&str> for FieldPurpose {
+    fn from(value: &str) -> Self {
+        match value {
+            "USERNAME" => Self::Username,
+            "PASSWORD" => Self::Password,
+            "NOTES" => Self::Notes,
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for FieldPurpose {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<FieldPurpose> for String {
+    fn from(purpose: FieldPurpose) -> Self {
+        purpose.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for FieldPurpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Character sets a generated value may draw from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CharacterSet {
+    /// Upper and lower case letters
+    Letters,
+    /// Digits
+    Digits,
+    /// Symbols
+    Symbols,
+}
+
+/// Describes how Connect should generate a value for a field.
+///
+/// ```
+/// use connect_1password::models::item::{CharacterSet, GeneratorRecipe};
+///
+/// // 20 characters, no symbols and no easily confused characters
+/// let recipe = GeneratorRecipe::new(20)
+///     .character_sets(&[CharacterSet::Letters, CharacterSet::Digits])
+///     .exclude_characters("0O1lI");
+/// assert!(recipe.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratorRecipe {
+    /// Length of the generated value.
+    pub length: u32,
+    /// Character sets the generated value draws from.
+    pub character_sets: Vec<CharacterSet>,
+    /// Characters which must not appear in the generated value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_characters: Option<String>,
+}
+
+impl GeneratorRecipe {
+    /// Shortest length accepted by Connect.
+    pub const MIN_LENGTH: u32 = 1;
+    /// Longest length accepted by Connect.
+    pub const MAX_LENGTH: u32 = 64;
+
+    /// Create a new instance of the given length, drawing from every character set.
+    pub fn new(length: u32) -> Self {
+        Self {
+            length,
+            character_sets: vec![
+                CharacterSet::Letters,
+                CharacterSet::Digits,
+                CharacterSet::Symbols,
+            ],
+            exclude_characters: None,
+        }
+    }
+
+    /// Specify the character sets to draw from
+    pub fn character_sets(mut self, sets: &[CharacterSet]) -> Self {
+        self.character_sets = sets.to_vec();
+        self
+    }
+
+    /// Specify characters to exclude
+    pub fn exclude_characters(mut self, characters: &str) -> Self {
+        self.exclude_characters = Some(characters.to_string());
+        self
+    }
+
+    fn problems(&self, field: &str) -> Vec<ValidationProblem> {
+        let mut problems = vec![];
+        if !(Self::MIN_LENGTH..=Self::MAX_LENGTH).contains(&self.length) {
+            let name = format!("{}.length", field);
+            problems.push(ValidationProblem::new(&name, ValidationCode::InvalidRecipe));
+        }
+        if self.character_sets.is_empty() {
+            let name = format!("{}.characterSets", field);
+            problems.push(ValidationProblem::new(&name, ValidationCode::InvalidRecipe));
+        }
+
+        problems
+    }
+
+    /// Check the recipe against Connect's limits: a length between [`Self::MIN_LENGTH`] and
+    /// [`Self::MAX_LENGTH`], and at least one character set.
+    pub fn validate(&self) -> Result<(), Error> {
+        let problems = self.problems("recipe");
+        if !problems.is_empty() {
+            return Err(Error::new_validation_error(ValidationError::new(problems)));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GeneratorRecipe {
+    /// Connect's default: 32 characters drawn from every character set.
+    fn default() -> Self {
+        Self::new(32)
     }
 }
 
 /// This is a Section Object
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct SectionObject {
     /// The UUID of the section.
     pub id: String,
     /// Some optional text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
 }
 
@@ -105,7 +509,7 @@ impl SectionObject {
 }
 
 /// This is a SectionID
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct SectionID {
     /// The UUID of the section.
     pub id: String,
@@ -120,34 +524,177 @@ impl SectionID {
     }
 }
 
+impl Default for SectionID {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// This is a FullItem
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct FullItem {
+    /// The ID of the item. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<ItemId>,
     /// The title of the item.
     pub title: String,
     /// An object containing an id property whose value is the UUID of the vault the item is in.
     pub vault: VaultID,
     /// The category of the item.
-    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<ItemCategory>,
     /// Vector of URL objects containing URLs for the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub urls: Option<Vec<UrlObject>>,
     /// Whether the item is marked as a favourite.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub favorite: Option<bool>,
     /// A vector of strings of the tags assigned to the item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
     /// A vector of Field objects of the fields to include with the item.
+    #[serde(default)]
     pub fields: Vec<FieldObject>,
     /// A vector of Section objects of the sections to include with the item.
+    #[serde(default)]
     pub sections: Vec<SectionObject>,
+    /// The version of the item. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    /// The state of the item. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Date and time when the item was created. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Date and time when the item was last changed. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// The ID of the user who last changed the item. Set by Connect on fetched items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_edited_by: Option<String>,
+}
+
+impl FullItem {
+    /// Returns the field with the given label, compared case-insensitively.
+    pub fn field_by_label(&self, label: &str) -> Result<&FieldObject, Error> {
+        self.find_field(&format!("label {:?}", label), |field| {
+            has_label(&field.label, label)
+        })
+    }
+
+    /// Returns the field with the given ID.
+    pub fn field_by_id(&self, id: &str) -> Result<&FieldObject, Error> {
+        self.find_field(&format!("id {:?}", id), |field| {
+            field.id.as_deref() == Some(id)
+        })
+    }
+
+    /// Returns the field with the given purpose.
+    pub fn field_by_purpose(&self, purpose: FieldPurpose) -> Result<&FieldObject, Error> {
+        self.find_field(&format!("purpose {}", purpose), |field| {
+            field.purpose.as_ref() == Some(&purpose)
+        })
+    }
+
+    /// Returns the field with the given label in the section with the given label, both compared
+    /// case-insensitively.
+    pub fn field_in_section(
+        &self,
+        section_label: &str,
+        label: &str,
+    ) -> Result<&FieldObject, Error> {
+        let section_ids: Vec<&str> = self
+            .sections
+            .iter()
+            .filter(|section| has_label(&section.label, section_label))
+            .map(|section| section.id.as_str())
+            .collect();
+
+        self.find_field(
+            &format!("section {:?} label {:?}", section_label, label),
+            |field| {
+                has_label(&field.label, label)
+                    && field
+                        .section
+                        .as_ref()
+                        .is_some_and(|section| section_ids.contains(&section.id.as_str()))
+            },
+        )
+    }
+
+    /// Returns the value of the username field.
+    pub fn username(&self) -> Result<&SecretString, Error> {
+        self.purpose_value(FieldPurpose::Username)
+    }
+
+    /// Returns the value of the password field.
+    pub fn password(&self) -> Result<&SecretString, Error> {
+        self.purpose_value(FieldPurpose::Password)
+    }
+
+    /// Returns the value of the notes field, e.g. the body of a secure note.
+    ///
+    /// An empty note is returned as an empty secret rather than an error, since Connect omits
+    /// the value of empty fields.
+    pub fn notes(&self) -> Result<&SecretString, Error> {
+        let field = self.field_by_purpose(FieldPurpose::Notes)?;
+
+        Ok(field.value.as_ref().unwrap_or(&EMPTY))
+    }
+
+    /// Returns the item's URLs, which may be empty.
+    pub fn urls(&self) -> &[UrlObject] {
+        self.urls.as_deref().unwrap_or_default()
+    }
+
+    fn purpose_value(&self, purpose: FieldPurpose) -> Result<&SecretString, Error> {
+        self.find_value(&format!("purpose {}", purpose), |field| {
+            field.purpose.as_ref() == Some(&purpose)
+        })
+    }
+
+    fn find_value<P>(&self, selector: &str, predicate: P) -> Result<&SecretString, Error>
+    where
+        P: Fn(&FieldObject) -> bool,
+    {
+        let field = self.find_field(selector, predicate)?;
+        field.value.as_ref().ok_or_else(|| {
+            Error::new_field_error(FieldError::new(selector, FieldErrorCode::MissingValue, 1))
+        })
+    }
+
+    fn find_field<P>(&self, selector: &str, predicate: P) -> Result<&FieldObject, Error>
+    where
+        P: Fn(&FieldObject) -> bool,
+    {
+        let mut matches = self.fields.iter().filter(|field| predicate(field));
+        match (matches.next(), matches.count()) {
+            (Some(field), 0) => Ok(field),
+            (None, _) => Err(Error::new_field_error(FieldError::new(
+                selector,
+                FieldErrorCode::NotFound,
+                0,
+            ))),
+            (Some(_), others) => Err(Error::new_field_error(FieldError::new(
+                selector,
+                FieldErrorCode::Ambiguous,
+                others + 1,
+            ))),
+        }
+    }
 }
 
 /// Defines a default interface
+#[deprecated(note = "use `ItemBuilder::custom`, whose `build` is available once titled")]
 pub trait DefaultItem {
     /// Execute the builder
-    fn build(&self) -> Result<FullItem, Box<dyn std::error::Error + Send + Sync>>;
+    fn build(&self) -> Result<FullItem, Error>;
 }
 
 /// Defines an interface for a Login item
+#[deprecated(note = "use `ItemBuilder::login`, whose `build` is available once titled")]
 pub trait LoginItem {
     /// Specify title
     fn title(self, username: &str) -> Self;
@@ -156,26 +703,54 @@ pub trait LoginItem {
     /// Specify password
     fn password(self, password: &str) -> Self;
     /// Execute the builder
-    fn build(&self) -> Result<FullItem, Box<dyn std::error::Error + Send + Sync>>;
+    fn build(&self) -> Result<FullItem, Error>;
 }
 
 /// Defines an interface for a Api Credential item
+#[deprecated(note = "use `ItemBuilder::api_credential`, whose `build` is available once titled")]
 pub trait ApiCredentialItem {
     /// Specify API key
     fn api_key(self, key: &str, title: &str) -> Self;
     /// Execute the builder
-    fn build(&self) -> Result<FullItem, Box<dyn std::error::Error + Send + Sync>>;
+    fn build(&self) -> Result<FullItem, Error>;
 }
 
-/// This is an ItemBuilder
+/// Builds a [`FullItem`] to add to a vault
+///
+/// `C` is the category, chosen with a constructor such as [`ItemBuilder::login`], and `T`
+/// records whether a title was given; `build` is only available once it has. Other required
+/// fields are checked by `build`. See the [`marker`] module.
+///
+/// ```
+/// use connect_1password::models::{item::ItemBuilder, VaultId};
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item = ItemBuilder::login(&vault_id)
+///     .title("Secure server login")
+///     .username("Bob")
+///     .password("hunter2")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(item.password().unwrap().expose(), "hunter2");
+/// ```
+///
+/// Without a title, `build` does not exist:
+///
+/// ```compile_fail
+/// use connect_1password::models::{item::ItemBuilder, VaultId};
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item = ItemBuilder::login(&vault_id).password("hunter2").build();
+/// ```
 #[derive(Debug)]
-pub struct ItemBuilder {
+pub struct ItemBuilder<C = Untyped, T = Untitled> {
     /// The title of the item.
     pub title: String,
     /// An object containing an id property whose value is the UUID of the vault the item is in.
     pub vault: VaultID,
     /// The category of the item.
-    pub category: Option<String>,
+    pub category: Option<ItemCategory>,
     /// Vector of URL objects containing URLs for the item.
     pub urls: Option<Vec<UrlObject>>,
     /// Whether the item is marked as a favourite.
@@ -186,58 +761,301 @@ pub struct ItemBuilder {
     pub fields: Vec<FieldObject>,
     /// A vector of Section objects of the sections to include with the item.
     pub sections: Vec<SectionObject>,
+    /// Problems found before `build`, e.g. a key which could not be generated.
+    problems: Vec<ValidationProblem>,
+    marker: PhantomData<(C, T)>,
 }
 
 /// Describes usable Item categories
-#[derive(Debug)]
+///
+/// Categories unknown to this version of the SDK are kept as [`ItemCategory::Unknown`], so items
+/// of newer categories can still be read and written back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
 pub enum ItemCategory {
     /// API Credential
     ApiCredential,
+    /// Bank Account
+    BankAccount,
+    /// Credit Card
+    CreditCard,
+    /// Custom
+    Custom,
+    /// Database
+    Database,
+    /// Document
+    Document,
+    /// Driver License
+    DriverLicense,
+    /// Email Account
+    EmailAccount,
+    /// Identity
+    Identity,
     /// Login
     Login,
+    /// Medical Record
+    MedicalRecord,
+    /// Membership
+    Membership,
+    /// Outdoor License
+    OutdoorLicense,
+    /// Passport
+    Passport,
     /// Password
     Password,
+    /// Reward Program
+    RewardProgram,
+    /// Secure Note
+    SecureNote,
+    /// Server
+    Server,
+    /// Social Security Number
+    SocialSecurityNumber,
+    /// Software License
+    SoftwareLicense,
+    /// SSH Key
+    SshKey,
+    /// Wireless Router
+    WirelessRouter,
+    /// A category not known to this SDK, holding the raw value
+    Unknown(String),
 }
 
 impl ItemCategory {
-    fn default() -> Self {
-        Self::ApiCredential
-    }
-}
-
-impl Into<String> for ItemCategory {
-    fn into(self) -> String {
-        let value = match self {
+    /// Returns the value used by the Connect API, e.g. `API_CREDENTIAL`.
+    pub fn as_str(&self) -> &str {
+        match self {
             Self::ApiCredential => "API_CREDENTIAL",
+            Self::BankAccount => "BANK_ACCOUNT",
+            Self::CreditCard => "CREDIT_CARD",
+            Self::Custom => "CUSTOM",
+            Self::Database => "DATABASE",
+            Self::Document => "DOCUMENT",
+            Self::DriverLicense => "DRIVER_LICENSE",
+            Self::EmailAccount => "EMAIL_ACCOUNT",
+            Self::Identity => "IDENTITY",
             Self::Login => "LOGIN",
+            Self::MedicalRecord => "MEDICAL_RECORD",
+            Self::Membership => "MEMBERSHIP",
+            Self::OutdoorLicense => "OUTDOOR_LICENSE",
+            Self::Passport => "PASSPORT",
             Self::Password => "PASSWORD",
-        };
+            Self::RewardProgram => "REWARD_PROGRAM",
+            Self::SecureNote => "SECURE_NOTE",
+            Self::Server => "SERVER",
+            Self::SocialSecurityNumber => "SOCIAL_SECURITY_NUMBER",
+            Self::SoftwareLicense => "SOFTWARE_LICENSE",
+            Self::SshKey => "SSH_KEY",
+            Self::WirelessRouter => "WIRELESS_ROUTER",
+            Self::Unknown(value) => value,
+        }
+    }
+}
 
-        value.to_string()
+impl From<&str> for ItemCategory {
+    fn from(value: &str) -> Self {
+        match value {
+            "API_CREDENTIAL" => Self::ApiCredential,
+            "BANK_ACCOUNT" => Self::BankAccount,
+            "CREDIT_CARD" => Self::CreditCard,
+            "CUSTOM" => Self::Custom,
+            "DATABASE" => Self::Database,
+            "DOCUMENT" => Self::Document,
+            "DRIVER_LICENSE" => Self::DriverLicense,
+            "EMAIL_ACCOUNT" => Self::EmailAccount,
+            "IDENTITY" => Self::Identity,
+            "LOGIN" => Self::Login,
+            "MEDICAL_RECORD" => Self::MedicalRecord,
+            "MEMBERSHIP" => Self::Membership,
+            "OUTDOOR_LICENSE" => Self::OutdoorLicense,
+            "PASSPORT" => Self::Passport,
+            "PASSWORD" => Self::Password,
+            "REWARD_PROGRAM" => Self::RewardProgram,
+            "SECURE_NOTE" => Self::SecureNote,
+            "SERVER" => Self::Server,
+            "SOCIAL_SECURITY_NUMBER" => Self::SocialSecurityNumber,
+            "SOFTWARE_LICENSE" => Self::SoftwareLicense,
+            "SSH_KEY" => Self::SshKey,
+            "WIRELESS_ROUTER" => Self::WirelessRouter,
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for ItemCategory {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<ItemCategory> for String {
+    fn from(category: ItemCategory) -> Self {
+        category.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for ItemCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
 impl ItemBuilder {
-    /// Create a new instance
-    pub fn new(vault_id: &str, category: ItemCategory) -> Self {
+    /// Create a new instance, used through the deprecated category traits
+    pub fn new(vault_id: &VaultId, category: ItemCategory) -> Self {
         let vault = VaultID {
-            id: vault_id.to_string(),
+            id: vault_id.clone(),
         };
 
         Self {
             vault,
             title: String::default(),
-            category: Some(category.into()),
+            category: Some(category),
             favorite: Some(false),
             urls: None,
             tags: None,
             fields: vec![],
             sections: vec![],
+            problems: vec![],
+            marker: PhantomData,
+        }
+    }
+
+    /// Create a builder for a Login item
+    pub fn login(vault_id: &VaultId) -> ItemBuilder<Login> {
+        Self::new(vault_id, ItemCategory::Login).retype()
+    }
+
+    /// Create a builder for an API Credential item
+    pub fn api_credential(vault_id: &VaultId) -> ItemBuilder<ApiCredential> {
+        Self::new(vault_id, ItemCategory::ApiCredential).retype()
+    }
+
+    /// Create a builder for a Credit Card item
+    pub fn credit_card(vault_id: &VaultId) -> ItemBuilder<CreditCard> {
+        Self::new(vault_id, ItemCategory::CreditCard).retype()
+    }
+
+    /// Create a builder for a Secure Note item
+    pub fn secure_note(vault_id: &VaultId) -> ItemBuilder<SecureNote> {
+        Self::new(vault_id, ItemCategory::SecureNote).retype()
+    }
+
+    /// Create a builder for an SSH Key item
+    pub fn ssh_key(vault_id: &VaultId) -> ItemBuilder<SshKey> {
+        Self::new(vault_id, ItemCategory::SshKey).retype()
+    }
+
+    /// Create a builder for a Database item
+    pub fn database(vault_id: &VaultId) -> ItemBuilder<Database> {
+        Self::new(vault_id, ItemCategory::Database).retype()
+    }
+
+    /// Create a builder for a Server item
+    pub fn server(vault_id: &VaultId) -> ItemBuilder<Server> {
+        Self::new(vault_id, ItemCategory::Server).retype()
+    }
+
+    /// Create a builder for an Identity item
+    pub fn identity(vault_id: &VaultId) -> ItemBuilder<Identity> {
+        Self::new(vault_id, ItemCategory::Identity).retype()
+    }
+
+    /// Create a builder for a Bank Account item
+    pub fn bank_account(vault_id: &VaultId) -> ItemBuilder<BankAccount> {
+        Self::new(vault_id, ItemCategory::BankAccount).retype()
+    }
+
+    /// Create a builder for an item of any category, without category-specific checks
+    pub fn custom(vault_id: &VaultId, category: ItemCategory) -> ItemBuilder<Custom> {
+        Self::new(vault_id, category).retype()
+    }
+}
+
+impl<T> ItemBuilder<Untyped, T> {
+    /// Specify title
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+}
+
+impl<C: Category, T> ItemBuilder<C, T> {
+    /// Specify title, which makes `build` available
+    pub fn title(self, title: &str) -> ItemBuilder<C, Titled> {
+        let mut builder = self.retype();
+        builder.title = title.to_string();
+        builder
+    }
+}
+
+impl<C, T> ItemBuilder<C, T> {
+    /// Add a URL. The first URL is primary until another is added with `primary` set, so
+    /// exactly one URL is primary.
+    ///
+    /// URLs are checked when the item is built.
+    pub fn url(mut self, url: &str, primary: bool) -> Self {
+        let urls = self.urls.get_or_insert_with(Vec::new);
+        if primary {
+            urls.iter_mut().for_each(|url| url.primary = false);
         }
+        let primary = primary || urls.is_empty();
+        urls.push(UrlObject {
+            url: url.trim().to_string(),
+            primary,
+            ..Default::default()
+        });
+
+        self
+    }
+
+    /// Add tags. Whitespace is collapsed, leading and trailing slashes of nested tags are
+    /// removed and tags already present, ignoring case, are skipped.
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let existing = self.tags.get_or_insert_with(Vec::new);
+        for tag in tags {
+            let tag = normalize_tag(tag.as_ref());
+            let duplicate = existing
+                .iter()
+                .any(|other| other.to_lowercase() == tag.to_lowercase());
+            if !tag.is_empty() && !duplicate {
+                existing.push(tag);
+            }
+        }
+
+        self
     }
 
-    // FIXME: This needs testing to ensure the OTP secret is applied correctly
-    pub(crate) fn add_otp(mut self, secret: &str) -> Self {
+    /// Mark the item as a favourite, or not
+    pub fn favorite(mut self, favorite: bool) -> Self {
+        self.favorite = Some(favorite);
+        self
+    }
+
+    /// Specify the notes, replacing any set before
+    pub fn notes(mut self, text: &str) -> Self {
+        self.fields
+            .retain(|field| field.purpose != Some(FieldPurpose::Notes));
+        self.fields.push(FieldObject {
+            id: Some("notesPlain".to_string()),
+            label: Some("notesPlain".to_string()),
+            purpose: Some(FieldPurpose::Notes),
+            r#type: Some(FieldType::String),
+            value: Some(SecretString::from(text)),
+            ..Default::default()
+        });
+
+        self
+    }
+
+    /// Add a one-time password field, given an `otpauth://totp/...` URI or a base32 secret.
+    ///
+    /// The value is checked with [`Totp`](crate::otp::Totp) when the item is built.
+    pub fn add_otp(mut self, otp: &str) -> Self {
         let section = SectionID::new();
         let section_obj = SectionObject::new(&section.id, "OTP");
 
@@ -245,11 +1063,12 @@ impl ItemBuilder {
 
         let field_object = FieldObject {
             section: Some(section),
-            label: None,
+            label: Some("one-time password".to_string()),
             purpose: None,
-            r#type: Some("OTP".to_string()),
-            generate: Some(true),
-            value: Some(secret.to_string()),
+            r#type: Some(FieldType::Otp),
+            generate: Some(false),
+            value: Some(SecretString::from(otp.trim())),
+            ..Default::default()
         };
         self.fields.push(field_object);
 
@@ -257,9 +1076,149 @@ impl ItemBuilder {
     }
 }
 
-impl DefaultItem for ItemBuilder {
-    fn build(&self) -> Result<FullItem, Box<dyn std::error::Error + Send + Sync>> {
+impl<C, T> ItemBuilder<C, T> {
+    /// Move the contents into a builder of another state.
+    fn retype<C2, T2>(self) -> ItemBuilder<C2, T2> {
+        ItemBuilder {
+            title: self.title,
+            vault: self.vault,
+            category: self.category,
+            urls: self.urls,
+            favorite: self.favorite,
+            tags: self.tags,
+            fields: self.fields,
+            sections: self.sections,
+            problems: self.problems,
+            marker: PhantomData,
+        }
+    }
+
+    fn has_field<P: Fn(&FieldObject) -> bool>(&self, predicate: P) -> bool {
+        self.fields.iter().any(predicate)
+    }
+
+    fn has_purpose(&self, purpose: FieldPurpose) -> bool {
+        self.has_field(|field| field.purpose.as_ref() == Some(&purpose))
+    }
+
+    /// Returns the section with the given template ID, adding it if missing.
+    fn template_section(&mut self, id: &str, label: &str) -> SectionID {
+        if !self.sections.iter().any(|section| section.id == id) {
+            self.sections.push(SectionObject::new(id, label));
+        }
+
+        SectionID { id: id.to_string() }
+    }
+
+    /// Add a field with a template ID, e.g. `ccnum`, in the given section.
+    fn template_field(
+        mut self,
+        section: Option<SectionID>,
+        id: &str,
+        label: &str,
+        r#type: FieldType,
+        value: &str,
+    ) -> Self {
+        self.fields.push(FieldObject {
+            id: Some(id.to_string()),
+            section,
+            label: Some(label.to_string()),
+            r#type: Some(r#type),
+            value: Some(SecretString::from(value)),
+            ..Default::default()
+        });
+
+        self
+    }
+
+    fn title_problem(&self) -> Option<ValidationProblem> {
+        self.title
+            .is_empty()
+            .then(|| ValidationProblem::new("title", ValidationCode::MissingTitle))
+    }
+
+    /// Problems shared by every category: duplicate field labels within a section, URLs,
+    /// one-time passwords, card numbers, dates and SSH keys which can't be parsed and oversized
+    /// values.
+    fn common_problems(&self) -> Vec<ValidationProblem> {
+        let mut problems = vec![];
+
+        let mut labels = HashSet::new();
+        for field in &self.fields {
+            if let Some(ref label) = field.label {
+                let section = field.section.as_ref().map(|section| &section.id);
+                if !labels.insert((section, label.to_lowercase())) {
+                    problems.push(ValidationProblem::new(
+                        label,
+                        ValidationCode::DuplicateFieldLabel,
+                    ));
+                }
+            }
+
+            if let Some(ref recipe) = field.recipe {
+                problems.extend(recipe.problems(&field_name(field)));
+            }
+
+            let format_problem =
+                credit_card::field_problem(field).or_else(|| ssh::field_problem(field));
+            if let Some(code) = format_problem {
+                problems.push(ValidationProblem::new(&field_name(field), code));
+            }
+
+            if field.r#type == Some(FieldType::Otp) && Totp::try_from(field).is_err() {
+                problems.push(ValidationProblem::new(
+                    &field_name(field),
+                    ValidationCode::InvalidOtp,
+                ));
+            }
+
+            let too_long = field
+                .value
+                .as_ref()
+                .is_some_and(|value| value.len() > MAX_VALUE_LENGTH);
+            if too_long {
+                problems.push(ValidationProblem::new(
+                    &field_name(field),
+                    ValidationCode::ValueTooLong,
+                ));
+            }
+        }
+
+        if self.title.len() > MAX_VALUE_LENGTH {
+            problems.push(ValidationProblem::new(
+                "title",
+                ValidationCode::ValueTooLong,
+            ));
+        }
+
+        for (i, url) in self.urls.iter().flatten().enumerate() {
+            if !is_valid_url(&url.url) {
+                let name = format!("urls[{}]", i);
+                problems.push(ValidationProblem::new(&name, ValidationCode::InvalidUrl));
+            }
+        }
+
+        let urls = self.urls.as_deref().unwrap_or_default();
+        if !urls.is_empty() && urls.iter().filter(|url| url.primary).count() != 1 {
+            problems.push(ValidationProblem::new(
+                "urls",
+                ValidationCode::InvalidPrimaryUrl,
+            ));
+        }
+
+        problems
+    }
+
+    /// Validate the builder, adding any category specific `problems`, and produce the item.
+    fn finish(&self, mut problems: Vec<ValidationProblem>) -> Result<FullItem, Error> {
+        problems.extend(self.problems.iter().cloned());
+        problems.extend(self.common_problems());
+        if !problems.is_empty() {
+            return Err(Error::new_validation_error(ValidationError::new(problems)));
+        }
+
         Ok(FullItem {
+            id: None,
             title: self.title.clone(),
             category: self.category.clone(),
             favorite: self.favorite,
@@ -268,64 +1227,109 @@ impl DefaultItem for ItemBuilder {
             tags: self.tags.clone(),
             urls: self.urls.clone(),
             vault: self.vault.clone(),
+            version: None,
+            state: None,
+            created_at: None,
+            updated_at: None,
+            last_edited_by: None,
         })
     }
 }
 
-impl LoginItem for ItemBuilder {
-    fn title(mut self, title: &str) -> Self {
-        self.title = title.to_string();
-        self
+/// Name a field in validation problems by its label, falling back to its purpose.
+fn field_name(field: &FieldObject) -> String {
+    match (&field.label, &field.purpose) {
+        (Some(label), _) => label.clone(),
+        (None, Some(purpose)) => purpose.as_str().to_lowercase(),
+        (None, None) => "value".to_string(),
     }
+}
 
-    fn username(mut self, username: &str) -> Self {
+/// Returns true if `value` matches `label`, ignoring ASCII case.
+fn has_label(value: &Option<String>, label: &str) -> bool {
+    value
+        .as_deref()
+        .is_some_and(|value| value.eq_ignore_ascii_case(label))
+}
+
+/// Collapse whitespace in `tag` and trim it, including slashes around nested tags.
+fn normalize_tag(tag: &str) -> String {
+    tag.split('/')
+        .map(|part| part.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns true if `url` is an absolute URL with a host.
+fn is_valid_url(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|url| url.has_host())
+}
+
+impl ItemBuilder<Custom, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        self.finish(self.title_problem().into_iter().collect())
+    }
+}
+
+impl<T> ItemBuilder<Login, T> {
+    /// Specify username
+    pub fn username(mut self, username: &str) -> Self {
         let field: FieldObject = FieldObject {
-            value: Some(username.to_string()),
-            purpose: Some("USERNAME".to_string()),
+            value: Some(SecretString::from(username)),
+            purpose: Some(FieldPurpose::Username),
             generate: None,
             label: None,
             r#type: None,
             section: None,
+            ..Default::default()
         };
 
         self.fields.push(field);
         self
     }
 
-    fn password(mut self, password: &str) -> Self {
+    /// Specify password; Connect generates one if it is empty
+    pub fn password(mut self, password: &str) -> Self {
         let field: FieldObject = FieldObject {
-            value: password.is_empty().then(|| password.to_string()),
-            purpose: Some("PASSWORD".to_string()),
-            generate: password.is_empty().then(|| true),
+            value: (!password.is_empty()).then(|| SecretString::from(password)),
+            purpose: Some(FieldPurpose::Password),
+            generate: password.is_empty().then_some(true),
             label: None,
             r#type: None,
             section: None,
+            ..Default::default()
         };
 
         self.fields.push(field);
         self
     }
 
-    fn build(&self) -> Result<FullItem, Box<dyn std::error::Error + Send + Sync>> {
-        if self.title.is_empty() {
-            return Err(Box::new(CustomError::new("Title is required")));
-        }
+    /// Have Connect generate the password according to `recipe`
+    pub fn generate_password(mut self, recipe: GeneratorRecipe) -> Self {
+        let field = FieldObject {
+            purpose: Some(FieldPurpose::Password),
+            generate: Some(true),
+            recipe: Some(recipe),
+            ..Default::default()
+        };
 
-        Ok(FullItem {
-            title: self.title.clone(),
-            category: self.category.clone(),
-            favorite: self.favorite,
-            fields: self.fields.clone(),
-            sections: self.sections.clone(),
-            tags: self.tags.clone(),
-            urls: self.urls.clone(),
-            vault: self.vault.clone(),
-        })
+        self.fields.push(field);
+        self
     }
 }
 
-impl ApiCredentialItem for ItemBuilder {
-    fn api_key(mut self, key: &str, title: &str) -> Self {
+impl ItemBuilder<Login, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        self.finish(self.login_problems())
+    }
+}
+
+impl<T> ItemBuilder<ApiCredential, T> {
+    /// Specify API key; Connect generates one if it is empty
+    pub fn api_key(mut self, key: &str) -> Self {
         let section = SectionID::new();
         let section_obj = SectionObject::new(&section.id, "API Key");
 
@@ -333,28 +1337,602 @@ impl ApiCredentialItem for ItemBuilder {
 
         let field_object = FieldObject {
             section: Some(section),
-            label: None,
+            label: Some("credential".to_string()),
             purpose: None,
-            r#type: Some(FieldType::Concealed.into()),
+            r#type: Some(FieldType::Concealed),
             generate: Some(key.is_empty()),
-            value: Some(key.to_string()),
+            value: Some(SecretString::from(key)),
+            ..Default::default()
         };
         self.fields.push(field_object);
-        self.title = title.to_string();
 
         self
     }
 
-    fn build(&self) -> Result<FullItem, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(FullItem {
-            title: self.title.clone(),
-            category: self.category.clone(),
-            favorite: self.favorite,
-            fields: self.fields.clone(),
-            sections: self.sections.clone(),
-            tags: self.tags.clone(),
-            urls: self.urls.clone(),
-            vault: self.vault.clone(),
-        })
+    /// Have Connect generate the API key according to `recipe`
+    pub fn generate_api_key(self, recipe: GeneratorRecipe) -> Self {
+        let mut builder = self.api_key("");
+        if let Some(field) = builder.fields.last_mut() {
+            field.recipe = Some(recipe);
+        }
+
+        builder
+    }
+}
+
+impl ItemBuilder<ApiCredential, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        self.finish(self.api_credential_problems())
+    }
+}
+
+impl<C, T> ItemBuilder<C, T> {
+    fn login_problems(&self) -> Vec<ValidationProblem> {
+        let mut problems: Vec<_> = self.title_problem().into_iter().collect();
+        if !self.has_purpose(FieldPurpose::Password) {
+            problems.push(ValidationProblem::new(
+                "password",
+                ValidationCode::MissingRequiredField,
+            ));
+        }
+
+        problems
+    }
+
+    fn api_credential_problems(&self) -> Vec<ValidationProblem> {
+        let mut problems: Vec<_> = self.title_problem().into_iter().collect();
+        if !self.has_field(|field| field.r#type == Some(FieldType::Concealed)) {
+            problems.push(ValidationProblem::new(
+                "credential",
+                ValidationCode::MissingRequiredField,
+            ));
+        }
+
+        problems
+    }
+}
+
+#[allow(deprecated)]
+impl DefaultItem for ItemBuilder {
+    fn build(&self) -> Result<FullItem, Error> {
+        self.finish(self.title_problem().into_iter().collect())
+    }
+}
+
+#[allow(deprecated)]
+impl LoginItem for ItemBuilder {
+    fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    fn username(self, username: &str) -> Self {
+        self.retype::<Login, Untitled>().username(username).retype()
+    }
+
+    fn password(self, password: &str) -> Self {
+        self.retype::<Login, Untitled>().password(password).retype()
+    }
+
+    fn build(&self) -> Result<FullItem, Error> {
+        self.finish(self.login_problems())
+    }
+}
+
+#[allow(deprecated)]
+impl ApiCredentialItem for ItemBuilder {
+    fn api_key(self, key: &str, title: &str) -> Self {
+        let mut builder: Self = self
+            .retype::<ApiCredential, Untitled>()
+            .api_key(key)
+            .retype();
+        builder.title = title.to_string();
+        builder
+    }
+
+    fn build(&self) -> Result<FullItem, Error> {
+        self.finish(self.api_credential_problems())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn codes(err: &Error) -> Vec<(String, ValidationCode)> {
+        err.validation_error()
+            .unwrap()
+            .problems
+            .iter()
+            .map(|problem| (problem.field.clone(), problem.code))
+            .collect()
+    }
+
+    fn vault_id() -> VaultId {
+        "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap()
+    }
+
+    #[test]
+    fn login_reports_every_problem() {
+        let err = ItemBuilder::login(&vault_id())
+            .title("")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(err.kind(), crate::error::ErrorKind::Validation);
+        assert_eq!(
+            codes(&err),
+            vec![
+                ("title".to_string(), ValidationCode::MissingTitle),
+                ("password".to_string(), ValidationCode::MissingRequiredField),
+            ]
+        );
+    }
+
+    #[test]
+    fn login_keeps_given_password() {
+        let builder = ItemBuilder::login(&vault_id())
+            .title("Login")
+            .username("Bob")
+            .password("hunter2");
+        let item = builder.build().unwrap();
+
+        assert_eq!(
+            item.fields[1].value.as_ref().map(SecretString::expose),
+            Some("hunter2")
+        );
+        assert_eq!(item.fields[1].generate, None);
+    }
+
+    #[test]
+    fn api_credential_requires_credential() {
+        let builder = ItemBuilder::api_credential(&vault_id()).title("");
+        let err = builder.build().unwrap_err();
+
+        assert_eq!(
+            codes(&err),
+            vec![
+                ("title".to_string(), ValidationCode::MissingTitle),
+                (
+                    "credential".to_string(),
+                    ValidationCode::MissingRequiredField
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_requires_title() {
+        let err = ItemBuilder::custom(&vault_id(), ItemCategory::Login)
+            .title("")
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            codes(&err),
+            vec![("title".to_string(), ValidationCode::MissingTitle)]
+        );
+    }
+
+    #[test]
+    fn common_problems() {
+        let mut builder =
+            ItemBuilder::custom(&vault_id(), ItemCategory::ApiCredential).title("Common");
+        let field = FieldObject {
+            section: None,
+            purpose: None,
+            r#type: None,
+            value: Some(SecretString::new("x".repeat(MAX_VALUE_LENGTH + 1))),
+            generate: None,
+            label: Some("host".to_string()),
+            ..Default::default()
+        };
+        builder.fields = vec![field.clone(), field];
+        builder.urls = Some(vec![
+            UrlObject {
+                url: "https://example.com".to_string(),
+                primary: true,
+                ..Default::default()
+            },
+            UrlObject {
+                url: "not a url".to_string(),
+                ..Default::default()
+            },
+        ]);
+
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![
+                ("host".to_string(), ValidationCode::ValueTooLong),
+                ("host".to_string(), ValidationCode::DuplicateFieldLabel),
+                ("host".to_string(), ValidationCode::ValueTooLong),
+                ("urls[1]".to_string(), ValidationCode::InvalidUrl),
+            ]
+        );
+    }
+
+    #[test]
+    fn item_category_serde() {
+        let json = serde_json::to_string(&ItemCategory::SecureNote).unwrap();
+        assert_eq!(json, r#""SECURE_NOTE""#);
+
+        let category: ItemCategory = serde_json::from_str(r#""SSH_KEY""#).unwrap();
+        assert_eq!(category, ItemCategory::SshKey);
+
+        let category: ItemCategory = serde_json::from_str(r#""CRYPTO_WALLET""#).unwrap();
+        assert_eq!(category, ItemCategory::Unknown("CRYPTO_WALLET".to_string()));
+        assert_eq!(
+            serde_json::to_string(&category).unwrap(),
+            r#""CRYPTO_WALLET""#
+        );
+    }
+
+    #[test]
+    fn item_data_category() {
+        let json = r#"{"id":"2fcbqwe9ndg175zg2dzwftvkpa","title":"Card","vault":{"id":"ytrfte14kw1uex5txn3pnaoerm"},"category":"CREDIT_CARD"}"#;
+        let item: ItemData = serde_json::from_str(json).unwrap();
+
+        assert_eq!(item.category, Some(ItemCategory::CreditCard));
+    }
+
+    #[test]
+    fn field_object_round_trip() {
+        let json = r#"[
+            {"id":"username","type":"STRING","purpose":"USERNAME","label":"username","value":"Bob"},
+            {"id":"password","type":"CONCEALED","purpose":"PASSWORD","label":"password","value":"x"},
+            {"id":"notesPlain","type":"STRING","purpose":"NOTES","label":"notesPlain"},
+            {"id":"one","section":{"id":"s"},"type":"TOTP","label":"one-time password"},
+            {"id":"two","section":{"id":"s"},"type":"SSHKEY","label":"private key"},
+            {"id":"three","section":{"id":"s"},"type":"FUTURE_TYPE","purpose":"FUTURE"}
+        ]"#;
+        let fields: Vec<FieldObject> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(fields[0].purpose, Some(FieldPurpose::Username));
+        assert_eq!(fields[1].r#type, Some(FieldType::Concealed));
+        assert_eq!(fields[2].purpose, Some(FieldPurpose::Notes));
+        assert_eq!(
+            fields[3].r#type,
+            Some(FieldType::Unknown("TOTP".to_string()))
+        );
+        assert_eq!(fields[4].r#type, Some(FieldType::SshKey));
+        assert_eq!(
+            fields[5].r#type,
+            Some(FieldType::Unknown("FUTURE_TYPE".to_string()))
+        );
+        assert_eq!(
+            fields[5].purpose,
+            Some(FieldPurpose::Unknown("FUTURE".to_string()))
+        );
+
+        let json = serde_json::to_string(&fields).unwrap();
+        let round_trip: Vec<FieldObject> = serde_json::from_str(&json).unwrap();
+        assert_eq!(round_trip, fields);
+        assert!(json.contains(r#""type":"FUTURE_TYPE""#));
+        assert!(json.contains(r#""type":"TOTP""#));
+    }
+
+    #[test]
+    fn generated_password_recipe() {
+        let recipe = GeneratorRecipe::new(20).character_sets(&[CharacterSet::Letters]);
+        let builder = ItemBuilder::login(&vault_id())
+            .title("Legacy system")
+            .generate_password(recipe);
+        let item = builder.build().unwrap();
+
+        let json = serde_json::to_value(&item.fields[0]).unwrap();
+        assert_eq!(json["generate"], true);
+        assert_eq!(
+            json["recipe"],
+            serde_json::json!({"length": 20, "characterSets": ["LETTERS"]})
+        );
+    }
+
+    #[test]
+    fn generated_api_key_recipe() {
+        let recipe = GeneratorRecipe::new(16).exclude_characters("O0");
+        let builder = ItemBuilder::api_credential(&vault_id())
+            .title("Dell XYZ")
+            .generate_api_key(recipe.clone());
+        let item = builder.build().unwrap();
+
+        assert_eq!(item.fields[0].generate, Some(true));
+        assert_eq!(item.fields[0].recipe, Some(recipe));
+    }
+
+    #[test]
+    fn recipe_limits() {
+        assert!(GeneratorRecipe::default().validate().is_ok());
+        assert!(GeneratorRecipe::new(64).validate().is_ok());
+
+        let err = GeneratorRecipe::new(0)
+            .character_sets(&[])
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![
+                ("recipe.length".to_string(), ValidationCode::InvalidRecipe),
+                (
+                    "recipe.characterSets".to_string(),
+                    ValidationCode::InvalidRecipe
+                ),
+            ]
+        );
+
+        let builder = ItemBuilder::login(&vault_id())
+            .title("Too long")
+            .generate_password(GeneratorRecipe::new(65));
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![("password.length".to_string(), ValidationCode::InvalidRecipe)]
+        );
+    }
+
+    #[test]
+    fn full_field_model() {
+        let json = r#"{
+            "title": "GitHub",
+            "vault": {"id": "ytrfte14kw1uex5txn3pnaoerm"},
+            "category": "LOGIN",
+            "sections": [{"id": "otp", "label": "One-time password"}],
+            "fields": [
+                {
+                    "id": "password",
+                    "type": "CONCEALED",
+                    "purpose": "PASSWORD",
+                    "label": "password",
+                    "value": "m7ZTfUahFq",
+                    "entropy": 59.54,
+                    "reference": "op://v/GitHub/password",
+                    "passwordDetails": {
+                        "entropy": 59,
+                        "generated": true,
+                        "strength": "VERY_GOOD",
+                        "history": ["older"]
+                    }
+                },
+                {
+                    "id": "TOTP_abc",
+                    "section": {"id": "otp"},
+                    "type": "OTP",
+                    "label": "one-time password",
+                    "value": "otpauth://totp/GitHub?secret=JBSWY3DPEHPK3PXP",
+                    "totp": "123456"
+                }
+            ]
+        }"#;
+        let item: FullItem = serde_json::from_str(json).unwrap();
+
+        let password = &item.fields[0];
+        assert_eq!(password.id.as_deref(), Some("password"));
+        assert_eq!(password.entropy, Some(59.54));
+        assert_eq!(
+            password.reference.as_deref(),
+            Some("op://v/GitHub/password")
+        );
+        let details = password.password_details.as_ref().unwrap();
+        assert_eq!(details.strength, Some(PasswordStrength::VeryGood));
+        assert_eq!(details.generated, Some(true));
+        assert_eq!(details.history, Some(vec![SecretString::from("older")]));
+
+        assert_eq!(
+            item.fields[1].totp.as_ref().map(SecretString::expose),
+            Some("123456")
+        );
+
+        let round_trip: FullItem =
+            serde_json::from_str(&serde_json::to_string(&item).unwrap()).unwrap();
+        assert_eq!(round_trip.fields, item.fields);
+    }
+
+    fn fetched_login() -> FullItem {
+        serde_json::from_str(
+            r#"{
+                "title": "GitHub",
+                "vault": {"id": "ytrfte14kw1uex5txn3pnaoerm"},
+                "category": "LOGIN",
+                "urls": [{"url": "https://github.com", "primary": true}],
+                "sections": [
+                    {"id": "add more"},
+                    {"id": "otp", "label": "One-time password"},
+                    {"id": "backup", "label": "Backup"}
+                ],
+                "fields": [
+                    {"id": "username", "type": "STRING", "purpose": "USERNAME", "label": "username", "value": "Bob"},
+                    {"id": "password", "type": "CONCEALED", "purpose": "PASSWORD", "label": "password", "value": "hunter2"},
+                    {"id": "notesPlain", "type": "STRING", "purpose": "NOTES", "label": "notesPlain"},
+                    {"id": "TOTP_a", "section": {"id": "otp"}, "type": "OTP", "label": "code", "value": "otpauth://totp/a"},
+                    {"id": "TOTP_b", "section": {"id": "backup"}, "type": "OTP", "label": "code", "value": "otpauth://totp/b"}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn field_error(err: Error) -> FieldError {
+        assert_eq!(err.kind(), crate::error::ErrorKind::Field);
+        err.field_error().unwrap().clone()
+    }
+
+    #[test]
+    fn field_accessors() {
+        let item = fetched_login();
+
+        assert_eq!(item.username().unwrap().expose(), "Bob");
+        assert_eq!(item.password().unwrap().expose(), "hunter2");
+        assert_eq!(item.urls()[0].url, "https://github.com");
+        assert_eq!(
+            item.field_by_id("TOTP_a").unwrap().r#type,
+            Some(FieldType::Otp)
+        );
+        assert_eq!(
+            item.field_by_label("PASSWORD").unwrap().id.as_deref(),
+            Some("password")
+        );
+
+        let code = item.field_in_section("backup", "Code").unwrap();
+        assert_eq!(code.id.as_deref(), Some("TOTP_b"));
+    }
+
+    #[test]
+    fn field_accessor_errors() {
+        let item = fetched_login();
+
+        let err = field_error(item.field_by_label("code").unwrap_err());
+        assert_eq!(err.code, FieldErrorCode::Ambiguous);
+        assert_eq!(err.matches, 2);
+
+        let err = field_error(item.field_by_id("missing").unwrap_err());
+        assert_eq!(err.code, FieldErrorCode::NotFound);
+        assert_eq!(err.selector, r#"id "missing""#);
+
+        let err = field_error(item.field_in_section("Other", "code").unwrap_err());
+        assert_eq!(err.code, FieldErrorCode::NotFound);
+
+        assert_eq!(item.notes().unwrap().expose(), "");
+
+        let mut item = item;
+        item.fields[1].value = None;
+        let err = field_error(item.password().unwrap_err());
+        assert_eq!(err.code, FieldErrorCode::MissingValue);
+        assert_eq!(err.to_string(), "field purpose PASSWORD has no value");
+
+        item.urls = None;
+        assert!(item.urls().is_empty());
+    }
+
+    #[test]
+    fn add_otp() {
+        let item = ItemBuilder::login(&vault_id())
+            .title("GitHub")
+            .password("hunter2")
+            .add_otp(" JBSWY3DPEHPK3PXP ")
+            .build()
+            .unwrap();
+
+        let otp = item.field_by_label("one-time password").unwrap();
+        assert_eq!(otp.r#type, Some(FieldType::Otp));
+        assert_eq!(otp.generate, Some(false));
+        assert_eq!(
+            otp.value.as_ref().map(SecretString::expose),
+            Some("JBSWY3DPEHPK3PXP")
+        );
+
+        let err = ItemBuilder::login(&vault_id())
+            .title("GitHub")
+            .password("hunter2")
+            .add_otp("otpauth://totp/GitHub?digits=6")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![("one-time password".to_string(), ValidationCode::InvalidOtp)]
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_traits() {
+        let builder = ItemBuilder::new(&vault_id(), ItemCategory::Login);
+        let legacy = LoginItem::build(
+            &LoginItem::title(builder, "Login")
+                .username("Bob")
+                .password("hunter2"),
+        )
+        .unwrap();
+        let typed = ItemBuilder::login(&vault_id())
+            .title("Login")
+            .username("Bob")
+            .password("hunter2")
+            .build()
+            .unwrap();
+        assert_eq!(legacy, typed);
+
+        let builder = ItemBuilder::new(&vault_id(), ItemCategory::ApiCredential);
+        let legacy = ApiCredentialItem::build(&builder.api_key("key", "Dell XYZ")).unwrap();
+        assert_eq!(legacy.title, "Dell XYZ");
+        assert_eq!(legacy.category, Some(ItemCategory::ApiCredential));
+        assert_eq!(
+            legacy.field_by_label("credential").unwrap().value,
+            Some(SecretString::from("key"))
+        );
+
+        let builder = ItemBuilder::new(&vault_id(), ItemCategory::Document);
+        let err = DefaultItem::build(&builder).unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![("title".to_string(), ValidationCode::MissingTitle)]
+        );
+        assert!(DefaultItem::build(&builder.title("Scan")).is_ok());
+    }
+
+    #[test]
+    fn item_details() {
+        let item = ItemBuilder::login(&vault_id())
+            .title("GitHub")
+            .password("hunter2")
+            .url("https://github.com", false)
+            .url("https://github.com/login", true)
+            .url(" https://gist.github.com ", false)
+            .tags(["work", " CI /deploy/ ", "Work", ""])
+            .tags(vec!["ci/deploy".to_string()])
+            .favorite(true)
+            .notes("first")
+            .notes("Recovery codes are in the safe")
+            .build()
+            .unwrap();
+
+        let urls: Vec<_> = item
+            .urls()
+            .iter()
+            .map(|url| (url.url.as_str(), url.primary))
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                ("https://github.com", false),
+                ("https://github.com/login", true),
+                ("https://gist.github.com", false),
+            ]
+        );
+        assert_eq!(
+            item.tags,
+            Some(vec!["work".to_string(), "CI/deploy".to_string()])
+        );
+        assert_eq!(item.favorite, Some(true));
+        assert_eq!(
+            item.notes().unwrap().expose(),
+            "Recovery codes are in the safe"
+        );
+    }
+
+    #[test]
+    fn url_problems() {
+        let builder = ItemBuilder::login(&vault_id())
+            .title("GitHub")
+            .password("hunter2")
+            .url("https://github.com", false);
+        assert!(builder.urls.as_ref().unwrap()[0].primary);
+
+        let mut builder = builder.url("github", false);
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![("urls[1]".to_string(), ValidationCode::InvalidUrl)]
+        );
+
+        builder.urls.as_mut().unwrap()[1] = UrlObject {
+            url: "https://github.com/login".to_string(),
+            primary: true,
+            ..Default::default()
+        };
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            codes(&err),
+            vec![("urls".to_string(), ValidationCode::InvalidPrimaryUrl)]
+        );
     }
 }
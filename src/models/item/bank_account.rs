@@ -0,0 +1,257 @@
+use super::{
+    marker::{BankAccount, Titled},
+    FieldType, FullItem, ItemBuilder,
+};
+use crate::{
+    error::{Error, ValidationCode, ValidationProblem},
+    secret::SecretString,
+};
+
+/// Builds a Bank Account item
+///
+/// Fields use the IDs and labels of 1Password's Bank Account template. Spaces are removed from
+/// the IBAN and SWIFT code, which are upper-cased and validated by `build`.
+///
+/// ```
+/// use connect_1password::models::{
+///     item::{FullItem, ItemBuilder},
+///     VaultId,
+/// };
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item: FullItem = ItemBuilder::bank_account(&vault_id)
+///     .title("Payout account")
+///     .bank_name("ACME Bank")
+///     .owner("Wendy Appleseed")
+///     .iban("GB82 WEST 1234 5698 7654 32")
+///     .swift("westgb2l")
+///     .build()
+///     .unwrap();
+///
+/// let iban = item.field_by_id("iban").unwrap();
+/// assert_eq!(iban.value.as_ref().unwrap().expose(), "GB82WEST12345698765432");
+/// ```
+impl<T> ItemBuilder<BankAccount, T> {
+    /// Specify the name of the bank
+    pub fn bank_name(self, name: &str) -> Self {
+        self.template_field(None, "bankName", "bank name", FieldType::String, name)
+    }
+
+    /// Specify the name on the account
+    pub fn owner(self, name: &str) -> Self {
+        self.template_field(None, "owner", "name on account", FieldType::String, name)
+    }
+
+    /// Specify the routing number
+    pub fn routing_number(self, number: &str) -> Self {
+        self.template_field(
+            None,
+            "routingNo",
+            "routing number",
+            FieldType::String,
+            number,
+        )
+    }
+
+    /// Specify the account number
+    pub fn account_number(self, number: &str) -> Self {
+        self.template_field(
+            None,
+            "accountNo",
+            "account number",
+            FieldType::Concealed,
+            number,
+        )
+    }
+
+    /// Specify the International Bank Account Number
+    pub fn iban(self, iban: &str) -> Self {
+        let iban = normalize_code(iban);
+
+        self.template_field(None, "iban", "IBAN", FieldType::String, &iban)
+    }
+
+    /// Specify the SWIFT (BIC) code of the bank
+    pub fn swift(self, code: &str) -> Self {
+        let code = normalize_code(code);
+
+        self.template_field(None, "swift", "SWIFT", FieldType::String, &code)
+    }
+}
+
+impl ItemBuilder<BankAccount, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        let mut problems: Vec<_> = self.title_problem().into_iter().collect();
+        for field in &self.fields {
+            let value = field.value.as_ref().map(SecretString::expose);
+            let problem = match (field.id.as_deref(), value) {
+                (Some("iban"), Some(iban)) if !is_valid_iban(iban) => {
+                    Some(ValidationCode::InvalidIban)
+                }
+                (Some("swift"), Some(code)) if !is_valid_swift(code) => {
+                    Some(ValidationCode::InvalidSwift)
+                }
+                _ => None,
+            };
+            if let Some(code) = problem {
+                let label = field.label.as_deref().unwrap_or_default();
+                problems.push(ValidationProblem::new(label, code));
+            }
+        }
+
+        self.finish(problems)
+    }
+}
+
+/// Remove whitespace and upper-case an IBAN or SWIFT code.
+fn normalize_code(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Returns true if `iban` is 15 to 34 characters, starts with a country code and passes the
+/// ISO 7064 mod 97-10 check.
+fn is_valid_iban(iban: &str) -> bool {
+    let bytes = iban.as_bytes();
+    let well_formed = (15..=34).contains(&bytes.len())
+        && bytes[..2].iter().all(u8::is_ascii_uppercase)
+        && bytes[2..4].iter().all(u8::is_ascii_digit)
+        && bytes[4..]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase());
+    if !well_formed {
+        return false;
+    }
+
+    // Move the country code and check digits to the end and read letters as 10 to 35
+    let remainder = bytes[4..]
+        .iter()
+        .chain(&bytes[..4])
+        .fold(0u32, |remainder, &b| match b {
+            b'0'..=b'9' => (remainder * 10 + u32::from(b - b'0')) % 97,
+            _ => (remainder * 100 + u32::from(b - b'A') + 10) % 97,
+        });
+
+    remainder == 1
+}
+
+/// Returns true if `code` is an 8 or 11 character SWIFT (BIC) code.
+fn is_valid_swift(code: &str) -> bool {
+    let bytes = code.as_bytes();
+
+    matches!(bytes.len(), 8 | 11)
+        && bytes[..6].iter().all(u8::is_ascii_uppercase)
+        && bytes[6..]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn builder() -> ItemBuilder<BankAccount, Titled> {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        ItemBuilder::bank_account(&vault_id).title("Payout account")
+    }
+
+    #[test]
+    fn bank_account_layout() {
+        let item = builder()
+            .bank_name("ACME Bank")
+            .owner("Wendy Appleseed")
+            .routing_number("011000015")
+            .account_number("12345678")
+            .iban("de89 3704 0044 0532 0130 00")
+            .swift("COBADEFFXXX")
+            .build()
+            .unwrap();
+
+        let layout: Vec<_> = item
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.id.as_deref().unwrap(),
+                    field.label.as_deref().unwrap(),
+                    field.r#type.clone().unwrap(),
+                    field.value.as_ref().unwrap().expose(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("bankName", "bank name", FieldType::String, "ACME Bank"),
+                (
+                    "owner",
+                    "name on account",
+                    FieldType::String,
+                    "Wendy Appleseed"
+                ),
+                (
+                    "routingNo",
+                    "routing number",
+                    FieldType::String,
+                    "011000015"
+                ),
+                (
+                    "accountNo",
+                    "account number",
+                    FieldType::Concealed,
+                    "12345678"
+                ),
+                ("iban", "IBAN", FieldType::String, "DE89370400440532013000"),
+                ("swift", "SWIFT", FieldType::String, "COBADEFFXXX"),
+            ]
+        );
+    }
+
+    #[test]
+    fn bank_account_problems() {
+        let err = builder()
+            .iban("GB82 WEST 1234 5698 7654 33")
+            .swift("WEST GB")
+            .build()
+            .unwrap_err();
+        let problems: Vec<_> = err
+            .validation_error()
+            .unwrap()
+            .problems
+            .iter()
+            .map(|problem| (problem.field.as_str(), problem.code))
+            .collect();
+
+        assert_eq!(
+            problems,
+            vec![
+                ("IBAN", ValidationCode::InvalidIban),
+                ("SWIFT", ValidationCode::InvalidSwift),
+            ]
+        );
+    }
+
+    #[test]
+    fn iban() {
+        assert!(is_valid_iban("GB82WEST12345698765432"));
+        assert!(is_valid_iban("NL91ABNA0417164300"));
+        assert!(is_valid_iban("NO9386011117947"));
+        assert!(!is_valid_iban("GB82WEST12345698765433"));
+        assert!(!is_valid_iban("GB82WEST1234569876543!"));
+        assert!(!is_valid_iban("82GBWEST12345698765432"));
+        assert!(!is_valid_iban("GB82"));
+    }
+
+    #[test]
+    fn swift() {
+        assert!(is_valid_swift("DEUTDEFF"));
+        assert!(is_valid_swift("DEUTDEFF500"));
+        assert!(!is_valid_swift("DEUTDEF"));
+        assert!(!is_valid_swift("DEU1DEFF"));
+        assert!(!is_valid_swift("DEUTDEFF50"));
+    }
+}
@@ -0,0 +1,386 @@
+use super::{
+    marker::{CreditCard, Titled},
+    FieldObject, FieldType, FullItem, ItemBuilder,
+};
+use crate::{
+    error::{Error, ValidationCode, ValidationProblem},
+    secret::SecretString,
+};
+use serde::{Deserialize, Serialize};
+
+/// Describes the type of a credit card, as stored in a `CREDIT_CARD_TYPE` field
+///
+/// Types unknown to this version of the SDK are kept as [`CardType::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum CardType {
+    /// Visa
+    Visa,
+    /// Mastercard
+    Mastercard,
+    /// American Express
+    AmericanExpress,
+    /// Diners Club
+    DinersClub,
+    /// Discover
+    Discover,
+    /// JCB
+    Jcb,
+    /// Maestro
+    Maestro,
+    /// UnionPay
+    UnionPay,
+    /// A type not known to this SDK, holding the raw value
+    Unknown(String),
+}
+
+impl CardType {
+    /// Returns the value used by 1Password, e.g. `mc`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Visa => "visa",
+            Self::Mastercard => "mc",
+            Self::AmericanExpress => "amex",
+            Self::DinersClub => "diners",
+            Self::Discover => "discover",
+            Self::Jcb => "jcb",
+            Self::Maestro => "maestro",
+            Self::UnionPay => "unionpay",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<&str> for CardType {
+    fn from(value: &str) -> Self {
+        match value {
+            "visa" => Self::Visa,
+            "mc" => Self::Mastercard,
+            "amex" => Self::AmericanExpress,
+            "diners" => Self::DinersClub,
+            "discover" => Self::Discover,
+            "jcb" => Self::Jcb,
+            "maestro" => Self::Maestro,
+            "unionpay" => Self::UnionPay,
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for CardType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<CardType> for String {
+    fn from(card_type: CardType) -> Self {
+        card_type.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for CardType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Builds a Credit Card item
+///
+/// Fields use the IDs and labels of 1Password's Credit Card template. Dates are `MONTH_YEAR`
+/// fields, given as `MM/YYYY`, `MM/YY` or `YYYYMM` and sent to Connect as `YYYYMM`.
+///
+/// ```
+/// use connect_1password::models::{
+///     item::{CardType, FullItem, ItemBuilder},
+///     VaultId,
+/// };
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item: FullItem = ItemBuilder::credit_card(&vault_id)
+///     .title("Corporate card")
+///     .cardholder("Wendy Appleseed")
+///     .card_type(CardType::Visa)
+///     .card_number("4111 1111 1111 1111")
+///     .verification_number("123")
+///     .expiry("07/2027")
+///     .issuing_bank("ACME Bank")
+///     .build()
+///     .unwrap();
+///
+/// let expiry = item.field_by_id("expiry").unwrap();
+/// assert_eq!(expiry.value.as_ref().unwrap().expose(), "202707");
+/// ```
+impl<T> ItemBuilder<CreditCard, T> {
+    /// Specify the cardholder's name
+    pub fn cardholder(self, name: &str) -> Self {
+        self.template_field(
+            None,
+            "cardholder",
+            "cardholder name",
+            FieldType::String,
+            name,
+        )
+    }
+
+    /// Specify the card type
+    pub fn card_type(self, card_type: CardType) -> Self {
+        self.template_field(
+            None,
+            "type",
+            "type",
+            FieldType::CreditCardType,
+            card_type.as_str(),
+        )
+    }
+
+    /// Specify the card number; spaces and dashes are removed
+    pub fn card_number(self, number: &str) -> Self {
+        let number: String = number.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+
+        self.template_field(
+            None,
+            "ccnum",
+            "number",
+            FieldType::CreditCardNumber,
+            &number,
+        )
+    }
+
+    /// Specify the verification number (CVV)
+    pub fn verification_number(self, number: &str) -> Self {
+        self.template_field(
+            None,
+            "cvv",
+            "verification number",
+            FieldType::Concealed,
+            number,
+        )
+    }
+
+    /// Specify the expiry date
+    pub fn expiry(self, month_year: &str) -> Self {
+        self.template_field(
+            None,
+            "expiry",
+            "expiry date",
+            FieldType::MonthYear,
+            &normalize_month_year(month_year),
+        )
+    }
+
+    /// Specify the date the card is valid from
+    pub fn valid_from(self, month_year: &str) -> Self {
+        self.template_field(
+            None,
+            "validFrom",
+            "valid from",
+            FieldType::MonthYear,
+            &normalize_month_year(month_year),
+        )
+    }
+
+    /// Specify the issuing bank
+    pub fn issuing_bank(mut self, bank: &str) -> Self {
+        let section = self.template_section("contactInfo", "Contact Information");
+
+        self.template_field(
+            Some(section),
+            "bank",
+            "issuing bank",
+            FieldType::String,
+            bank,
+        )
+    }
+}
+
+impl ItemBuilder<CreditCard, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        let problems: Vec<ValidationProblem> = self.title_problem().into_iter().collect();
+
+        self.finish(problems)
+    }
+}
+
+/// Convert `MM/YYYY`, `MM/YY`, `YYYY-MM` and `YYYY/MM` to Connect's `YYYYMM`, leaving anything
+/// else unchanged for validation to report.
+fn normalize_month_year(value: &str) -> String {
+    let value = value.trim();
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+    match value.split_once(['/', '-']) {
+        Some((month, year)) if digits(month) && digits(year) && month.len() <= 2 => {
+            match year.len() {
+                4 => format!("{}{:0>2}", year, month),
+                2 => format!("20{}{:0>2}", year, month),
+                _ => value.to_string(),
+            }
+        }
+        Some((year, month)) if digits(year) && digits(month) && year.len() == 4 => {
+            format!("{}{:0>2}", year, month)
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Returns true if `value` is a `YYYYMM` date.
+fn is_valid_month_year(value: &str) -> bool {
+    value.len() == 6
+        && value.bytes().all(|b| b.is_ascii_digit())
+        && matches!(value[4..].parse::<u8>(), Ok(1..=12))
+}
+
+/// Returns true if `number` is 12 to 19 digits and passes the Luhn check.
+// `u32::is_multiple_of` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn is_valid_card_number(number: &str) -> bool {
+    if !(12..=19).contains(&number.len()) || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    let sum: u32 = number
+        .bytes()
+        .rev()
+        .map(|b| u32::from(b - b'0'))
+        .enumerate()
+        .map(|(i, digit)| match (i % 2, digit * 2) {
+            (0, _) => digit,
+            (_, doubled) if doubled > 9 => doubled - 9,
+            (_, doubled) => doubled,
+        })
+        .sum();
+
+    sum % 10 == 0
+}
+
+/// Problems with the format of credit card numbers and `MONTH_YEAR` dates.
+pub(super) fn field_problem(field: &FieldObject) -> Option<ValidationCode> {
+    let value = field.value.as_ref().map(SecretString::expose)?;
+    match field.r#type {
+        Some(FieldType::CreditCardNumber) if !is_valid_card_number(value) => {
+            Some(ValidationCode::InvalidCardNumber)
+        }
+        Some(FieldType::MonthYear) if !is_valid_month_year(value) => {
+            Some(ValidationCode::InvalidMonthYear)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::item::SectionObject;
+
+    fn builder() -> ItemBuilder<CreditCard, Titled> {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        ItemBuilder::credit_card(&vault_id).title("Corporate card")
+    }
+
+    #[test]
+    fn credit_card_layout() {
+        let item = builder()
+            .cardholder("Wendy Appleseed")
+            .card_type(CardType::Mastercard)
+            .card_number("5555-5555-5555-4444")
+            .verification_number("123")
+            .expiry("7/27")
+            .valid_from("2023-01")
+            .issuing_bank("ACME Bank")
+            .build()
+            .unwrap();
+
+        let layout: Vec<_> = item
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.id.as_deref().unwrap(),
+                    field.label.as_deref().unwrap(),
+                    field.r#type.clone().unwrap(),
+                    field.value.as_ref().unwrap().expose(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                (
+                    "cardholder",
+                    "cardholder name",
+                    FieldType::String,
+                    "Wendy Appleseed"
+                ),
+                ("type", "type", FieldType::CreditCardType, "mc"),
+                (
+                    "ccnum",
+                    "number",
+                    FieldType::CreditCardNumber,
+                    "5555555555554444"
+                ),
+                ("cvv", "verification number", FieldType::Concealed, "123"),
+                ("expiry", "expiry date", FieldType::MonthYear, "202707"),
+                ("validFrom", "valid from", FieldType::MonthYear, "202301"),
+                ("bank", "issuing bank", FieldType::String, "ACME Bank"),
+            ]
+        );
+        assert_eq!(
+            item.field_in_section("Contact Information", "issuing bank")
+                .unwrap()
+                .id
+                .as_deref(),
+            Some("bank")
+        );
+        assert_eq!(
+            item.sections,
+            vec![SectionObject::new("contactInfo", "Contact Information")]
+        );
+    }
+
+    #[test]
+    fn credit_card_problems() {
+        let err = builder()
+            .card_number("4111 1111 1111 1112")
+            .expiry("13/2027")
+            .valid_from("soon")
+            .build()
+            .unwrap_err();
+        let problems: Vec<_> = err
+            .validation_error()
+            .unwrap()
+            .problems
+            .iter()
+            .map(|problem| (problem.field.as_str(), problem.code))
+            .collect();
+
+        assert_eq!(
+            problems,
+            vec![
+                ("number", ValidationCode::InvalidCardNumber),
+                ("expiry date", ValidationCode::InvalidMonthYear),
+                ("valid from", ValidationCode::InvalidMonthYear),
+            ]
+        );
+    }
+
+    #[test]
+    fn luhn() {
+        assert!(is_valid_card_number("4111111111111111"));
+        assert!(is_valid_card_number("378282246310005"));
+        assert!(!is_valid_card_number("4111111111111112"));
+        assert!(!is_valid_card_number("4111"));
+        assert!(!is_valid_card_number("4111x11111111111"));
+    }
+
+    #[test]
+    fn month_year() {
+        assert_eq!(normalize_month_year("07/2027"), "202707");
+        assert_eq!(normalize_month_year("7/27"), "202707");
+        assert_eq!(normalize_month_year("2027/07"), "202707");
+        assert_eq!(normalize_month_year("202707"), "202707");
+        assert!(is_valid_month_year("202712"));
+        assert!(!is_valid_month_year("202700"));
+        assert!(!is_valid_month_year("07/2027"));
+    }
+}
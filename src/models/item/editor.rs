@@ -0,0 +1,569 @@
+use super::{field_name, has_label, FieldObject, FieldType, FullItem, ItemBuilder, ItemCategory};
+use crate::{
+    error::{
+        Error, FieldError, FieldErrorCode, ValidationCode, ValidationError, ValidationProblem,
+    },
+    models::{ItemId, VaultId},
+    secret::{SecretString, REDACTED},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Edits an item fetched from Connect, see [`ItemBuilder::edit`]
+///
+/// The item keeps its ID, version, sections and field IDs. Fields are looked up by label,
+/// compared case-insensitively; a label matching no field or several fields makes `build` and
+/// `patch` fail with the first such [`FieldError`].
+#[derive(Debug)]
+pub struct ItemEditor {
+    original: FullItem,
+    item: FullItem,
+    error: Option<FieldError>,
+}
+
+/// Whether a [`PatchOperation`] adds, removes or replaces a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PatchOp {
+    /// Add a value, e.g. a new field
+    Add,
+    /// Remove a value
+    Remove,
+    /// Replace a value
+    Replace,
+}
+
+/// A JSON Patch (RFC 6902) operation on an item, as accepted by Connect
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+pub struct PatchOperation {
+    /// The kind of operation
+    pub op: PatchOp,
+    /// The JSON pointer to the changed value, e.g. `/fields/password/value`
+    pub path: String,
+    /// The new value, absent for [`PatchOp::Remove`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+impl std::fmt::Debug for PatchOperation {
+    /// Values may hold secrets and are redacted.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PatchOperation")
+            .field("op", &self.op)
+            .field("path", &self.path)
+            .field("value", &self.value.as_ref().map(|_| REDACTED))
+            .finish()
+    }
+}
+
+/// The changes made by an [`ItemEditor`], to be applied with
+/// [`items::patch`](crate::items::patch)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemPatch {
+    /// The vault the item is in
+    pub vault_id: VaultId,
+    /// The ID of the edited item
+    pub item_id: ItemId,
+    /// The operations, empty if nothing changed
+    pub operations: Vec<PatchOperation>,
+}
+
+impl ItemBuilder {
+    /// Create an editor for an item fetched with [`items::get`](crate::items::get)
+    ///
+    /// ```
+    /// use connect_1password::models::item::{FieldType, FullItem, ItemBuilder, PatchOp};
+    ///
+    /// # let item: FullItem = serde_json::from_str(r#"{
+    /// #     "id": "2fcbqwe9ndg175zg2dzwftvkpa",
+    /// #     "title": "Secure server login",
+    /// #     "vault": {"id": "ytrfte14kw1uex5txn3pnaoerm"},
+    /// #     "category": "LOGIN",
+    /// #     "version": 3,
+    /// #     "fields": [
+    /// #         {"id": "password", "purpose": "PASSWORD", "type": "CONCEALED",
+    /// #          "label": "password", "value": "hunter2"}
+    /// #     ]
+    /// # }"#).unwrap();
+    /// // `item` was fetched with `items::get`
+    /// let editor = ItemBuilder::edit(item)
+    ///     .set("password", "correct horse battery staple")
+    ///     .field("pin", FieldType::Concealed, "1234");
+    ///
+    /// let patch = editor.patch().unwrap();
+    /// assert_eq!(patch.operations[0].op, PatchOp::Replace);
+    /// assert_eq!(patch.operations[0].path, "/fields/password/value");
+    /// assert_eq!(patch.operations[1].op, PatchOp::Add);
+    ///
+    /// let item = editor.build().unwrap();
+    /// assert_eq!(item.password().unwrap().expose(), "correct horse battery staple");
+    /// ```
+    pub fn edit(item: FullItem) -> ItemEditor {
+        ItemEditor {
+            original: item.clone(),
+            item,
+            error: None,
+        }
+    }
+}
+
+impl ItemEditor {
+    /// Specify title
+    pub fn title(mut self, title: &str) -> Self {
+        self.item.title = title.to_string();
+        self
+    }
+
+    /// Mark the item as a favourite, or not
+    pub fn favorite(mut self, favorite: bool) -> Self {
+        self.item.favorite = Some(favorite);
+        self
+    }
+
+    /// Set the value of the field with the given label
+    pub fn set(mut self, label: &str, value: &str) -> Self {
+        if let Some(field) = self.field_mut(label) {
+            field.value = Some(SecretString::from(value));
+            field.generate = None;
+            field.recipe = None;
+        }
+
+        self
+    }
+
+    /// Remove the field with the given label
+    pub fn remove(mut self, label: &str) -> Self {
+        if let Some(index) = self.position(label) {
+            self.item.fields.remove(index);
+        }
+
+        self
+    }
+
+    /// Rename the field with the given label, keeping its ID and value
+    pub fn rename(mut self, label: &str, new_label: &str) -> Self {
+        if let Some(field) = self.field_mut(label) {
+            field.label = Some(new_label.to_string());
+        }
+
+        self
+    }
+
+    /// Add a field outside any section, with a new random ID
+    pub fn field(mut self, label: &str, r#type: FieldType, value: &str) -> Self {
+        self.item.fields.push(FieldObject {
+            id: Some(Uuid::new_v4().to_simple().to_string()),
+            label: Some(label.to_string()),
+            r#type: Some(r#type),
+            value: Some(SecretString::from(value)),
+            ..Default::default()
+        });
+
+        self
+    }
+
+    /// Execute the editor, producing the whole item to send to
+    /// [`items::replace`](crate::items::replace)
+    ///
+    /// The title, the labels of all fields and the values of the fields added or changed are
+    /// validated like [`ItemBuilder`] does; other values are left as fetched.
+    pub fn build(&self) -> Result<FullItem, Error> {
+        self.check()?;
+
+        Ok(self.item.clone())
+    }
+
+    /// Execute the editor, producing only the changes, to send to
+    /// [`items::patch`](crate::items::patch)
+    ///
+    /// Changed values and labels replace just that part of a field; other changes to a field
+    /// replace it whole.
+    pub fn patch(&self) -> Result<ItemPatch, Error> {
+        self.check()?;
+        let mut problems = self.unaddressable_changes();
+        if self.original.id.is_none() {
+            problems.insert(0, ValidationProblem::new("id", ValidationCode::MissingId));
+        }
+        let item_id = match self.original.id {
+            Some(ref id) if problems.is_empty() => id.clone(),
+            _ => return Err(Error::new_validation_error(ValidationError::new(problems))),
+        };
+
+        let mut operations = vec![];
+        if self.item.title != self.original.title {
+            operations.push(replace("/title", &self.item.title)?);
+        }
+        if self.item.favorite != self.original.favorite {
+            operations.push(replace("/favorite", &self.item.favorite)?);
+        }
+
+        for field in &self.original.fields {
+            if let Some(ref id) = field.id {
+                if !self.item.fields.iter().any(|edited| edited.id == field.id) {
+                    operations.push(PatchOperation {
+                        op: PatchOp::Remove,
+                        path: field_path(id),
+                        value: None,
+                    });
+                }
+            }
+        }
+
+        for field in &self.item.fields {
+            // Fields without an ID are unchanged, see `unaddressable_changes`
+            let Some(ref id) = field.id else { continue };
+            let path = field_path(id);
+            let original = self
+                .original
+                .fields
+                .iter()
+                .find(|original| original.id == field.id);
+
+            match original {
+                None => operations.push(PatchOperation {
+                    op: PatchOp::Add,
+                    path: "/fields".to_string(),
+                    value: Some(serde_json::to_value(field)?),
+                }),
+                Some(original) if original == field => {}
+                Some(original) => {
+                    let relabelled = FieldObject {
+                        label: original.label.clone(),
+                        value: original.value.clone(),
+                        generate: original.generate,
+                        recipe: original.recipe.clone(),
+                        ..field.clone()
+                    };
+                    if relabelled != *original {
+                        operations.push(replace(&path, field)?);
+                        continue;
+                    }
+                    if field.value != original.value {
+                        operations.push(replace(&format!("{}/value", path), &field.value)?);
+                    }
+                    if field.label != original.label {
+                        operations.push(replace(&format!("{}/label", path), &field.label)?);
+                    }
+                }
+            }
+        }
+
+        Ok(ItemPatch {
+            vault_id: self.original.vault.id.clone(),
+            item_id,
+            operations,
+        })
+    }
+
+    /// Report changes to fields without an ID, which a patch can't address: fields changed or,
+    /// failing that, removed.
+    fn unaddressable_changes(&self) -> Vec<ValidationProblem> {
+        let without_id = |fields: &[FieldObject]| -> Vec<FieldObject> {
+            fields
+                .iter()
+                .filter(|field| field.id.is_none())
+                .cloned()
+                .collect()
+        };
+        let (before, after) = (
+            without_id(&self.original.fields),
+            without_id(&self.item.fields),
+        );
+        let changed: Vec<_> = after
+            .iter()
+            .filter(|field| !before.contains(field))
+            .collect();
+        let removed = before.iter().filter(|field| !after.contains(field));
+        let fields: Vec<_> = if changed.is_empty() {
+            removed.collect()
+        } else {
+            changed
+        };
+
+        fields
+            .into_iter()
+            .map(|field| ValidationProblem::new(&field_name(field), ValidationCode::MissingId))
+            .collect()
+    }
+
+    /// Returns the field with the given label, recording an error unless there is exactly one.
+    fn field_mut(&mut self, label: &str) -> Option<&mut FieldObject> {
+        let index = self.position(label)?;
+
+        self.item.fields.get_mut(index)
+    }
+
+    fn position(&mut self, label: &str) -> Option<usize> {
+        let mut matches = self
+            .item
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| has_label(&field.label, label))
+            .map(|(index, _)| index);
+        let (code, count) = match (matches.next(), matches.count()) {
+            (Some(index), 0) => return Some(index),
+            (None, _) => (FieldErrorCode::NotFound, 0),
+            (Some(_), others) => (FieldErrorCode::Ambiguous, others + 1),
+        };
+
+        let selector = format!("label {:?}", label);
+        self.error
+            .get_or_insert_with(|| FieldError::new(&selector, code, count));
+        None
+    }
+
+    /// Fail on the first lookup error, then validate the title, the labels of all fields and
+    /// the values of the fields added or changed.
+    fn check(&self) -> Result<(), Error> {
+        if let Some(ref err) = self.error {
+            return Err(Error::new_field_error(err.clone()));
+        }
+
+        let category = self.item.category.clone().unwrap_or(ItemCategory::Custom);
+        let mut builder =
+            ItemBuilder::custom(&self.item.vault.id, category).title(&self.item.title);
+        let mut problems: Vec<_> = builder.title_problem().into_iter().collect();
+
+        builder.fields = self.item.fields.clone();
+        let duplicate_labels = builder
+            .common_problems()
+            .into_iter()
+            .filter(|problem| problem.code == ValidationCode::DuplicateFieldLabel);
+        problems.extend(duplicate_labels);
+
+        builder.fields = self
+            .item
+            .fields
+            .iter()
+            .filter(|field| !self.original.fields.contains(field))
+            .cloned()
+            .collect();
+        let value_problems = builder
+            .common_problems()
+            .into_iter()
+            .filter(|problem| problem.code != ValidationCode::DuplicateFieldLabel);
+        problems.extend(value_problems);
+
+        if !problems.is_empty() {
+            return Err(Error::new_validation_error(ValidationError::new(problems)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the JSON pointer to the field with the given ID.
+fn field_path(id: &str) -> String {
+    // Escape as RFC 6901 requires
+    format!("/fields/{}", id.replace('~', "~0").replace('/', "~1"))
+}
+
+fn replace<V: Serialize>(path: &str, value: &V) -> Result<PatchOperation, Error> {
+    Ok(PatchOperation {
+        op: PatchOp::Replace,
+        path: path.to_string(),
+        value: Some(serde_json::to_value(value)?),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::item::SectionObject;
+    use serde_json::json;
+
+    fn fetched() -> FullItem {
+        serde_json::from_value(json!({
+            "id": "2fcbqwe9ndg175zg2dzwftvkpa",
+            "title": "Build server",
+            "vault": {"id": "ytrfte14kw1uex5txn3pnaoerm"},
+            "category": "SERVER",
+            "version": 7,
+            "favorite": false,
+            "sections": [{"id": "admin_console", "label": "Admin Console"}],
+            "fields": [
+                {"id": "url", "type": "URL", "label": "URL", "value": "https://ci.example.com"},
+                {"id": "username", "type": "STRING", "label": "username", "value": "admin"},
+                {"id": "password", "type": "CONCEALED", "label": "password", "value": "hunter2"},
+                {"id": "console_password", "section": {"id": "admin_console"},
+                 "type": "CONCEALED", "label": "console password", "value": "s3cret"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn keeps_identity() {
+        let item = ItemBuilder::edit(fetched())
+            .set("Password", "correct horse")
+            .rename("console password", "root password")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            item.id.as_ref().unwrap().as_str(),
+            "2fcbqwe9ndg175zg2dzwftvkpa"
+        );
+        assert_eq!(item.version, Some(7));
+        assert_eq!(
+            item.sections,
+            vec![SectionObject::new("admin_console", "Admin Console")]
+        );
+        let password = item.field_by_id("password").unwrap();
+        assert_eq!(password.value.as_ref().unwrap().expose(), "correct horse");
+        let renamed = item
+            .field_in_section("Admin Console", "root password")
+            .unwrap();
+        assert_eq!(renamed.id.as_deref(), Some("console_password"));
+    }
+
+    #[test]
+    fn patch_operations() {
+        let patch = ItemBuilder::edit(fetched())
+            .title("CI server")
+            .set("password", "correct horse")
+            .rename("username", "login")
+            .remove("console password")
+            .field("pin", FieldType::Concealed, "1234")
+            .patch()
+            .unwrap();
+
+        assert_eq!(patch.vault_id.as_str(), "ytrfte14kw1uex5txn3pnaoerm");
+        assert_eq!(patch.item_id.as_str(), "2fcbqwe9ndg175zg2dzwftvkpa");
+        let operations: Vec<_> = patch
+            .operations
+            .iter()
+            .map(|operation| (operation.op, operation.path.as_str()))
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                (PatchOp::Replace, "/title"),
+                (PatchOp::Remove, "/fields/console_password"),
+                (PatchOp::Replace, "/fields/username/label"),
+                (PatchOp::Replace, "/fields/password/value"),
+                (PatchOp::Add, "/fields"),
+            ]
+        );
+
+        let body = serde_json::to_value(&patch.operations).unwrap();
+        assert_eq!(
+            body[3],
+            json!({"op": "replace", "path": "/fields/password/value", "value": "correct horse"})
+        );
+        assert_eq!(
+            body[1],
+            json!({"op": "remove", "path": "/fields/console_password"})
+        );
+        assert_eq!(body[4]["value"]["label"], "pin");
+        assert!(!format!("{:?}", patch).contains("correct horse"));
+    }
+
+    #[test]
+    fn unchanged() {
+        let patch = ItemBuilder::edit(fetched()).patch().unwrap();
+
+        assert!(patch.operations.is_empty());
+    }
+
+    #[test]
+    fn lookup_errors() {
+        let err = ItemBuilder::edit(fetched())
+            .set("token", "abc")
+            .remove("password")
+            .build()
+            .unwrap_err();
+        let err = err.field_error().unwrap();
+        assert_eq!(err.code, FieldErrorCode::NotFound);
+        assert_eq!(err.selector, "label \"token\"");
+
+        let mut item = fetched();
+        item.fields[1].label = Some("Password".to_string());
+        let err = ItemBuilder::edit(item)
+            .remove("password")
+            .patch()
+            .unwrap_err();
+        let err = err.field_error().unwrap();
+        assert_eq!(err.code, FieldErrorCode::Ambiguous);
+        assert_eq!(err.matches, 2);
+    }
+
+    #[test]
+    fn fields_without_id() {
+        let mut item = fetched();
+        item.fields[1].id = None;
+        item.fields[2].id = None;
+
+        for (editor, label) in [
+            (
+                ItemBuilder::edit(item.clone()).set("username", "root"),
+                "username",
+            ),
+            (
+                ItemBuilder::edit(item.clone()).rename("password", "secret"),
+                "secret",
+            ),
+            (
+                ItemBuilder::edit(item.clone()).remove("username"),
+                "username",
+            ),
+        ] {
+            let err = editor.patch().unwrap_err();
+            let problems = &err.validation_error().unwrap().problems;
+            assert_eq!(
+                problems,
+                &vec![ValidationProblem::new(label, ValidationCode::MissingId)]
+            );
+        }
+
+        let patch = ItemBuilder::edit(item.clone())
+            .set("url", "https://ci2.example.com")
+            .patch()
+            .unwrap();
+        assert_eq!(patch.operations.len(), 1);
+        assert!(ItemBuilder::edit(item)
+            .set("username", "root")
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn edit_problems() {
+        let err = ItemBuilder::edit(fetched())
+            .title("")
+            .field("one-time password", FieldType::Otp, "not base32!")
+            .build()
+            .unwrap_err();
+        let codes: Vec<_> = err
+            .validation_error()
+            .unwrap()
+            .problems
+            .iter()
+            .map(|problem| problem.code)
+            .collect();
+        assert_eq!(
+            codes,
+            vec![ValidationCode::MissingTitle, ValidationCode::InvalidOtp]
+        );
+
+        for editor in [
+            ItemBuilder::edit(fetched()).rename("username", "Password"),
+            ItemBuilder::edit(fetched()).field("password", FieldType::Concealed, "x"),
+        ] {
+            let err = editor.patch().unwrap_err();
+            let problems = &err.validation_error().unwrap().problems;
+            assert_eq!(problems.len(), 1);
+            assert_eq!(problems[0].code, ValidationCode::DuplicateFieldLabel);
+        }
+
+        let mut item = fetched();
+        item.id = None;
+        let err = ItemBuilder::edit(item).patch().unwrap_err();
+        assert_eq!(
+            err.validation_error().unwrap().problems[0].code,
+            ValidationCode::MissingId
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use super::{
+    marker::{Identity, Titled},
+    FieldType, FullItem, ItemBuilder,
+};
+use crate::error::{Error, ValidationProblem};
+use chrono::NaiveDate;
+
+/// A postal address; parts left as `None` are omitted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Address {
+    /// Street and house number
+    pub street: Option<String>,
+    /// City or town
+    pub city: Option<String>,
+    /// State, province or region
+    pub state: Option<String>,
+    /// Postal or ZIP code
+    pub zip: Option<String>,
+    /// Country, e.g. `us`
+    pub country: Option<String>,
+}
+
+impl std::fmt::Display for Address {
+    /// Formats the address on one line, with the parts separated by commas.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts = [
+            &self.street,
+            &self.city,
+            &self.state,
+            &self.zip,
+            &self.country,
+        ];
+        let parts: Vec<_> = parts
+            .into_iter()
+            .flatten()
+            .map(|part| part.trim())
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        f.write_str(&parts.join(", "))
+    }
+}
+
+impl<T> ItemBuilder<Identity, T> {
+    /// Add a field to the Identification section.
+    fn identification_field(mut self, id: &str, label: &str, value: &str) -> Self {
+        let section = self.template_section("name", "Identification");
+
+        self.template_field(Some(section), id, label, FieldType::String, value)
+    }
+}
+
+/// Builds an Identity item
+///
+/// Fields use the IDs, labels and sections of 1Password's Identity template.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use connect_1password::models::{
+///     item::{Address, FullItem, ItemBuilder},
+///     VaultId,
+/// };
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item: FullItem = ItemBuilder::identity(&vault_id)
+///     .title("Wendy Appleseed")
+///     .first_name("Wendy")
+///     .last_name("Appleseed")
+///     .date_of_birth(NaiveDate::from_ymd_opt(1990, 4, 1).unwrap())
+///     .address(Address {
+///         street: Some("1 Infinite Loop".to_string()),
+///         city: Some("Cupertino".to_string()),
+///         country: Some("us".to_string()),
+///         ..Default::default()
+///     })
+///     .email("wendy@example.com")
+///     .build()
+///     .unwrap();
+///
+/// let birth_date = item.field_in_section("Identification", "birth date").unwrap();
+/// assert_eq!(birth_date.value.as_ref().unwrap().expose(), "1990-04-01");
+/// ```
+impl<T> ItemBuilder<Identity, T> {
+    /// Specify the first name
+    pub fn first_name(self, name: &str) -> Self {
+        self.identification_field("firstname", "first name", name)
+    }
+
+    /// Specify the middle initial
+    pub fn initial(self, initial: &str) -> Self {
+        self.identification_field("initial", "initial", initial)
+    }
+
+    /// Specify the last name
+    pub fn last_name(self, name: &str) -> Self {
+        self.identification_field("lastname", "last name", name)
+    }
+
+    /// Specify the date of birth
+    pub fn date_of_birth(mut self, date: NaiveDate) -> Self {
+        let section = self.template_section("name", "Identification");
+
+        self.template_field(
+            Some(section),
+            "birthdate",
+            "birth date",
+            FieldType::Date,
+            &date.format("%Y-%m-%d").to_string(),
+        )
+    }
+
+    /// Specify the postal address
+    pub fn address(mut self, address: Address) -> Self {
+        let section = self.template_section("address", "Address");
+
+        self.template_field(
+            Some(section),
+            "address",
+            "address",
+            FieldType::Address,
+            &address.to_string(),
+        )
+    }
+
+    /// Specify the default phone number
+    pub fn phone(mut self, number: &str) -> Self {
+        let section = self.template_section("address", "Address");
+
+        self.template_field(
+            Some(section),
+            "defphone",
+            "default phone",
+            FieldType::Phone,
+            number,
+        )
+    }
+
+    /// Specify the email address
+    pub fn email(mut self, email: &str) -> Self {
+        let section = self.template_section("internet", "Internet Details");
+
+        self.template_field(Some(section), "email", "email", FieldType::Email, email)
+    }
+}
+
+impl ItemBuilder<Identity, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        let problems: Vec<ValidationProblem> = self.title_problem().into_iter().collect();
+
+        self.finish(problems)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::item::SectionObject;
+
+    #[test]
+    fn identity_layout() {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let item = ItemBuilder::identity(&vault_id)
+            .title("Contractor")
+            .first_name("Wendy")
+            .initial("J")
+            .last_name("Appleseed")
+            .date_of_birth(NaiveDate::from_ymd_opt(1990, 4, 1).unwrap())
+            .address(Address {
+                street: Some("1 Infinite Loop".to_string()),
+                city: Some("Cupertino".to_string()),
+                state: Some("CA".to_string()),
+                zip: Some(" ".to_string()),
+                ..Default::default()
+            })
+            .phone("+1 555 0100")
+            .email("wendy@example.com")
+            .build()
+            .unwrap();
+
+        let layout: Vec<_> = item
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.section.as_ref().unwrap().id.as_str(),
+                    field.id.as_deref().unwrap(),
+                    field.r#type.clone().unwrap(),
+                    field.value.as_ref().unwrap().expose(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("name", "firstname", FieldType::String, "Wendy"),
+                ("name", "initial", FieldType::String, "J"),
+                ("name", "lastname", FieldType::String, "Appleseed"),
+                ("name", "birthdate", FieldType::Date, "1990-04-01"),
+                (
+                    "address",
+                    "address",
+                    FieldType::Address,
+                    "1 Infinite Loop, Cupertino, CA"
+                ),
+                ("address", "defphone", FieldType::Phone, "+1 555 0100"),
+                ("internet", "email", FieldType::Email, "wendy@example.com"),
+            ]
+        );
+        assert_eq!(
+            item.sections,
+            vec![
+                SectionObject::new("name", "Identification"),
+                SectionObject::new("address", "Address"),
+                SectionObject::new("internet", "Internet Details"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Type-level states of an [`ItemBuilder`](super::ItemBuilder)
+//!
+//! The first parameter of an `ItemBuilder` is its category, chosen by constructors such as
+//! [`ItemBuilder::login`](super::ItemBuilder::login); the second records whether a title was
+//! given. Only a titled builder of a known category has a `build` method.
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A category known at compile time, implemented by the category markers in this module
+pub trait Category: sealed::Sealed {}
+
+macro_rules! category {
+    ($($(#[$doc:meta])* $name:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct $name;
+
+            impl sealed::Sealed for $name {}
+            impl Category for $name {}
+        )*
+    };
+}
+
+category! {
+    /// A Login item
+    Login;
+    /// An API Credential item
+    ApiCredential;
+    /// A Credit Card item
+    CreditCard;
+    /// A Secure Note item
+    SecureNote;
+    /// An SSH Key item
+    SshKey;
+    /// A Database item
+    Database;
+    /// A Server item
+    Server;
+    /// An Identity item
+    Identity;
+    /// A Bank Account item
+    BankAccount;
+    /// Any category, chosen at runtime, without category-specific fields or checks
+    Custom;
+}
+
+/// A builder from [`ItemBuilder::new`](super::ItemBuilder::new), whose category is only known
+/// at runtime and which is used through the deprecated category traits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Untyped;
+
+/// A builder without a title
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Untitled;
+
+/// A builder with a title
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Titled;
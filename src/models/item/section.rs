@@ -0,0 +1,193 @@
+use super::{FieldObject, FieldType, GeneratorRecipe, ItemBuilder, SectionID, SectionObject};
+use crate::secret::SecretString;
+use uuid::Uuid;
+
+/// Adds fields to a section of an item, see [`ItemBuilder::section`]
+///
+/// Every field gets a new random ID. Labels must be unique within a section, which is checked
+/// when the item is built.
+#[derive(Debug)]
+pub struct SectionBuilder {
+    section: SectionID,
+    fields: Vec<FieldObject>,
+}
+
+impl SectionBuilder {
+    /// Add a field of any type with the given value
+    pub fn field(self, label: &str, r#type: FieldType, value: &str) -> Self {
+        self.push(FieldObject {
+            label: Some(label.to_string()),
+            r#type: Some(r#type),
+            value: Some(SecretString::from(value)),
+            ..Default::default()
+        })
+    }
+
+    /// Add a concealed field, e.g. a token or password
+    pub fn concealed(self, label: &str, value: &str) -> Self {
+        self.field(label, FieldType::Concealed, value)
+    }
+
+    /// Add a field of any type whose value Connect generates using `recipe`
+    pub fn generate(self, label: &str, r#type: FieldType, recipe: GeneratorRecipe) -> Self {
+        self.push(FieldObject {
+            label: Some(label.to_string()),
+            r#type: Some(r#type),
+            generate: Some(true),
+            recipe: Some(recipe),
+            ..Default::default()
+        })
+    }
+
+    fn push(mut self, field: FieldObject) -> Self {
+        self.fields.push(FieldObject {
+            id: Some(Uuid::new_v4().to_simple().to_string()),
+            section: Some(self.section.clone()),
+            ..field
+        });
+
+        self
+    }
+}
+
+impl<C, T> ItemBuilder<C, T> {
+    /// Add fields to the section with the given label, which is created if the item has none
+    /// by that label yet.
+    ///
+    /// ```
+    /// use connect_1password::models::{
+    ///     item::{FieldType, GeneratorRecipe, ItemBuilder},
+    ///     VaultId,
+    /// };
+    ///
+    /// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+    /// let item = ItemBuilder::secure_note(&vault_id)
+    ///     .title("Deployment")
+    ///     .section("Production", |s| {
+    ///         s.field("host", FieldType::String, "prod.example.com")
+    ///             .concealed("token", "s3cret")
+    ///             .generate("signing key", FieldType::Concealed, GeneratorRecipe::default())
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(item.field_in_section("Production", "token").is_ok());
+    /// ```
+    pub fn section<F>(mut self, label: &str, fields: F) -> Self
+    where
+        F: FnOnce(SectionBuilder) -> SectionBuilder,
+    {
+        let existing = self.sections.iter().find(|section| {
+            section
+                .label
+                .as_deref()
+                .is_some_and(|existing| existing.eq_ignore_ascii_case(label))
+        });
+        let section = match existing {
+            Some(section) => SectionID {
+                id: section.id.clone(),
+            },
+            None => {
+                let section = SectionID::new();
+                self.sections.push(SectionObject::new(&section.id, label));
+                section
+            }
+        };
+
+        let builder = fields(SectionBuilder {
+            section,
+            fields: vec![],
+        });
+        self.fields.extend(builder.fields);
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        error::ValidationCode,
+        models::item::{
+            marker::{Custom, Titled},
+            ItemCategory,
+        },
+    };
+
+    fn builder() -> ItemBuilder<Custom, Titled> {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        ItemBuilder::custom(&vault_id, ItemCategory::SecureNote).title("Deployment")
+    }
+
+    #[test]
+    fn section_fields() {
+        let item = builder()
+            .section("Production", |s| {
+                s.field("host", FieldType::String, "prod.example.com")
+                    .concealed("token", "s3cret")
+            })
+            .section("Staging", |s| s.concealed("token", "test"))
+            .section("production", |s| {
+                s.generate(
+                    "signing key",
+                    FieldType::Concealed,
+                    GeneratorRecipe::default(),
+                )
+                .generate("account", FieldType::String, GeneratorRecipe::new(12))
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(item.sections.len(), 2);
+        let production = &item.sections[0].id;
+        let fields: Vec<_> = item
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    &field.section.as_ref().unwrap().id == production,
+                    field.label.as_deref().unwrap(),
+                    field.r#type.clone().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (true, "host", FieldType::String),
+                (true, "token", FieldType::Concealed),
+                (false, "token", FieldType::Concealed),
+                (true, "signing key", FieldType::Concealed),
+                (true, "account", FieldType::String),
+            ]
+        );
+
+        let generated = item.field_in_section("Production", "signing key").unwrap();
+        assert_eq!(generated.generate, Some(true));
+        assert_eq!(generated.recipe, Some(GeneratorRecipe::default()));
+        assert_eq!(generated.value, None);
+        let account = item.field_in_section("Production", "account").unwrap();
+        assert_eq!(account.generate, Some(true));
+        assert_eq!(account.recipe, Some(GeneratorRecipe::new(12)));
+
+        let ids: std::collections::HashSet<_> =
+            item.fields.iter().map(|field| field.id.clone()).collect();
+        assert_eq!(ids.len(), 5);
+        assert!(!ids.contains(&None));
+    }
+
+    #[test]
+    fn duplicate_labels() {
+        let err = builder()
+            .section("Production", |s| s.concealed("token", "a"))
+            .section("Production", |s| s.concealed("Token", "b"))
+            .build()
+            .unwrap_err();
+        let problems = &err.validation_error().unwrap().problems;
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "Token");
+        assert_eq!(problems[0].code, ValidationCode::DuplicateFieldLabel);
+    }
+}
@@ -0,0 +1,101 @@
+use super::{
+    marker::{SecureNote, Titled},
+    FullItem, ItemBuilder,
+};
+use crate::error::{Error, ValidationProblem};
+
+/// Builds a Secure Note item
+///
+/// ```
+/// use connect_1password::models::{
+///     item::{FullItem, ItemBuilder},
+///     VaultId,
+/// };
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item: FullItem = ItemBuilder::secure_note(&vault_id)
+///     .title("Failover runbook")
+///     .note("1. Promote the replica\n2. Rotate the database password")
+///     .section("Credentials", |s| s.concealed("database password", "hunter2"))
+///     .build()
+///     .unwrap();
+///
+/// assert!(item.notes().unwrap().expose().starts_with("1. Promote"));
+/// ```
+impl<T> ItemBuilder<SecureNote, T> {
+    /// Specify the body of the note
+    pub fn note(self, text: &str) -> Self {
+        self.notes(text)
+    }
+}
+
+impl ItemBuilder<SecureNote, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        let problems: Vec<ValidationProblem> = self.title_problem().into_iter().collect();
+
+        self.finish(problems)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        error::{FieldErrorCode, ValidationCode},
+        models::item::{FieldPurpose, FieldType, ItemCategory},
+        secret::SecretString,
+    };
+
+    fn builder() -> ItemBuilder<SecureNote> {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        ItemBuilder::secure_note(&vault_id)
+    }
+
+    #[test]
+    fn secure_note_layout() {
+        let item = builder()
+            .title("Runbook")
+            .note("Restart the service")
+            .section("Database", |s| {
+                s.field("host", FieldType::String, "db.internal")
+            })
+            .build()
+            .unwrap();
+
+        let note = item.field_by_id("notesPlain").unwrap();
+        assert_eq!(note.purpose, Some(FieldPurpose::Notes));
+        assert_eq!(note.section, None);
+        assert_eq!(item.notes().unwrap().expose(), "Restart the service");
+        assert_eq!(
+            item.field_in_section("Database", "host")
+                .unwrap()
+                .value
+                .as_ref()
+                .map(SecretString::expose),
+            Some("db.internal")
+        );
+        assert_eq!(item.category, Some(ItemCategory::SecureNote));
+    }
+
+    #[test]
+    fn secure_note_requires_title() {
+        let err = builder().title("").note("text").build().unwrap_err();
+
+        assert!(err
+            .validation_error()
+            .unwrap()
+            .has(ValidationCode::MissingTitle));
+    }
+
+    #[test]
+    fn empty_note() {
+        let mut item = builder().title("Empty").note("").build().unwrap();
+        item.fields[0].value = None;
+        assert_eq!(item.notes().unwrap().expose(), "");
+
+        item.fields.clear();
+        let err = item.notes().unwrap_err();
+        assert_eq!(err.field_error().unwrap().code, FieldErrorCode::NotFound);
+    }
+}
@@ -0,0 +1,281 @@
+use super::{
+    is_valid_url,
+    marker::{Server, Titled},
+    FieldType, FullItem, ItemBuilder, SectionID,
+};
+use crate::{
+    error::{Error, ValidationCode, ValidationProblem},
+    secret::SecretString,
+};
+
+/// The admin console of a Server item; fields left as `None` are omitted
+#[derive(Debug, Clone, Default)]
+pub struct AdminConsole {
+    /// URL of the admin console
+    pub url: Option<String>,
+    /// Username for the admin console
+    pub username: Option<String>,
+    /// Password for the admin console
+    pub password: Option<SecretString>,
+}
+
+/// The hosting provider of a Server item; fields left as `None` are omitted
+#[derive(Debug, Clone, Default)]
+pub struct HostingProvider {
+    /// Name of the provider
+    pub name: Option<String>,
+    /// The provider's website
+    pub website: Option<String>,
+    /// URL for support requests
+    pub support_url: Option<String>,
+    /// Phone number for support requests
+    pub support_phone: Option<String>,
+}
+
+/// Builds a Server item
+///
+/// Fields use the IDs and labels of 1Password's Server template; the admin console and hosting
+/// provider are kept in their own sections.
+///
+/// ```
+/// use connect_1password::models::{
+///     item::{AdminConsole, FullItem, HostingProvider, ItemBuilder},
+///     VaultId,
+/// };
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item: FullItem = ItemBuilder::server(&vault_id)
+///     .title("Build server")
+///     .server_url("https://ci.example.com")
+///     .credentials("admin", "hunter2")
+///     .admin_console(AdminConsole {
+///         url: Some("https://ci.example.com:8443".to_string()),
+///         ..Default::default()
+///     })
+///     .hosting_provider(HostingProvider {
+///         name: Some("ACME Hosting".to_string()),
+///         support_phone: Some("+1 555 0100".to_string()),
+///         ..Default::default()
+///     })
+///     .build()
+///     .unwrap();
+///
+/// assert!(item.field_in_section("Hosting Provider", "name").is_ok());
+/// ```
+impl<T> ItemBuilder<Server, T> {
+    /// Specify the URL of the server
+    pub fn server_url(self, url: &str) -> Self {
+        self.template_field(None, "url", "URL", FieldType::Url, url)
+    }
+
+    /// Specify the username and password
+    pub fn credentials(self, username: &str, password: &str) -> Self {
+        self.template_field(None, "username", "username", FieldType::String, username)
+            .template_field(None, "password", "password", FieldType::Concealed, password)
+    }
+
+    /// Add the admin console section
+    pub fn admin_console(mut self, console: AdminConsole) -> Self {
+        let section = self.template_section("admin_console", "Admin Console");
+        let fields = [
+            (
+                "admin_console_url",
+                "admin console URL",
+                FieldType::Url,
+                console.url.as_deref(),
+            ),
+            (
+                "admin_console_username",
+                "admin console username",
+                FieldType::String,
+                console.username.as_deref(),
+            ),
+            (
+                "console_password",
+                "console password",
+                FieldType::Concealed,
+                console.password.as_ref().map(SecretString::expose),
+            ),
+        ];
+
+        self.section_fields(section, fields)
+    }
+
+    /// Add the hosting provider section
+    pub fn hosting_provider(mut self, provider: HostingProvider) -> Self {
+        let section = self.template_section("hosting_provider_details", "Hosting Provider");
+        let fields = [
+            ("name", "name", FieldType::String, provider.name.as_deref()),
+            (
+                "website",
+                "website",
+                FieldType::Url,
+                provider.website.as_deref(),
+            ),
+            (
+                "support_contact_url",
+                "support URL",
+                FieldType::Url,
+                provider.support_url.as_deref(),
+            ),
+            (
+                "support_contact_phone",
+                "support phone",
+                FieldType::Phone,
+                provider.support_phone.as_deref(),
+            ),
+        ];
+
+        self.section_fields(section, fields)
+    }
+}
+
+impl ItemBuilder<Server, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        let mut problems: Vec<_> = self.title_problem().into_iter().collect();
+        for field in &self.fields {
+            let url = field.value.as_ref().map(SecretString::expose);
+            if field.r#type == Some(FieldType::Url) && !url.is_some_and(is_valid_url) {
+                let label = field.label.as_deref().unwrap_or("URL");
+                problems.push(ValidationProblem::new(label, ValidationCode::InvalidUrl));
+            }
+        }
+
+        self.finish(problems)
+    }
+}
+
+impl<T> ItemBuilder<Server, T> {
+    /// Add the fields with a value to `section`.
+    fn section_fields<const N: usize>(
+        self,
+        section: SectionID,
+        fields: [(&str, &str, FieldType, Option<&str>); N],
+    ) -> Self {
+        fields
+            .into_iter()
+            .fold(self, |builder, (id, label, r#type, value)| match value {
+                Some(value) => {
+                    builder.template_field(Some(section.clone()), id, label, r#type, value)
+                }
+                None => builder,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::item::SectionObject;
+
+    fn builder() -> ItemBuilder<Server, Titled> {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        ItemBuilder::server(&vault_id).title("Build server")
+    }
+
+    #[test]
+    fn server_layout() {
+        let item = builder()
+            .server_url("https://ci.example.com")
+            .credentials("admin", "hunter2")
+            .admin_console(AdminConsole {
+                url: Some("https://ci.example.com:8443".to_string()),
+                username: Some("root".to_string()),
+                password: Some(SecretString::from("s3cret")),
+            })
+            .hosting_provider(HostingProvider {
+                name: Some("ACME Hosting".to_string()),
+                support_url: Some("https://support.example.com".to_string()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+
+        let layout: Vec<_> = item
+            .fields
+            .iter()
+            .map(|field| {
+                (
+                    field.section.as_ref().map(|section| section.id.as_str()),
+                    field.id.as_deref().unwrap(),
+                    field.r#type.clone().unwrap(),
+                    field.value.as_ref().unwrap().expose(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                (None, "url", FieldType::Url, "https://ci.example.com"),
+                (None, "username", FieldType::String, "admin"),
+                (None, "password", FieldType::Concealed, "hunter2"),
+                (
+                    Some("admin_console"),
+                    "admin_console_url",
+                    FieldType::Url,
+                    "https://ci.example.com:8443"
+                ),
+                (
+                    Some("admin_console"),
+                    "admin_console_username",
+                    FieldType::String,
+                    "root"
+                ),
+                (
+                    Some("admin_console"),
+                    "console_password",
+                    FieldType::Concealed,
+                    "s3cret"
+                ),
+                (
+                    Some("hosting_provider_details"),
+                    "name",
+                    FieldType::String,
+                    "ACME Hosting"
+                ),
+                (
+                    Some("hosting_provider_details"),
+                    "support_contact_url",
+                    FieldType::Url,
+                    "https://support.example.com"
+                ),
+            ]
+        );
+        assert_eq!(
+            item.sections,
+            vec![
+                SectionObject::new("admin_console", "Admin Console"),
+                SectionObject::new("hosting_provider_details", "Hosting Provider"),
+            ]
+        );
+    }
+
+    #[test]
+    fn server_problems() {
+        let err = ItemBuilder::server(&"ytrfte14kw1uex5txn3pnaoerm".parse().unwrap())
+            .title("")
+            .server_url("ci.example.com")
+            .hosting_provider(HostingProvider {
+                website: Some("https://example.com".to_string()),
+                ..Default::default()
+            })
+            .build()
+            .unwrap_err();
+        let problems: Vec<_> = err
+            .validation_error()
+            .unwrap()
+            .problems
+            .iter()
+            .map(|problem| (problem.field.as_str(), problem.code))
+            .collect();
+
+        assert_eq!(
+            problems,
+            vec![
+                ("title", ValidationCode::MissingTitle),
+                ("URL", ValidationCode::InvalidUrl),
+            ]
+        );
+    }
+}
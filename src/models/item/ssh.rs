@@ -0,0 +1,271 @@
+use super::{
+    marker::{SshKey, Titled},
+    FieldObject, FieldType, FullItem, ItemBuilder,
+};
+use crate::{
+    error::{Error, ValidationCode, ValidationProblem},
+    secret::SecretString,
+};
+use log::error;
+use ssh_key::{rand_core::OsRng, Algorithm, HashAlg, LineEnding, PrivateKey};
+use std::{fs, io::Write, path::Path};
+use zeroize::Zeroizing;
+
+impl<T> ItemBuilder<SshKey, T> {
+    fn ssh_key_fields(self, openssh: &str, key: Option<&PrivateKey>) -> Self {
+        let builder = self.template_field(
+            None,
+            "private_key",
+            "private key",
+            FieldType::SshKey,
+            openssh,
+        );
+        let public_key = key.and_then(|key| key.public_key().to_openssh().ok());
+
+        match (key, public_key) {
+            (Some(key), Some(public_key)) => builder
+                .template_field(
+                    None,
+                    "public_key",
+                    "public key",
+                    FieldType::String,
+                    &public_key,
+                )
+                .template_field(
+                    None,
+                    "fingerprint",
+                    "fingerprint",
+                    FieldType::String,
+                    &key.fingerprint(HashAlg::Sha256).to_string(),
+                ),
+            _ => builder,
+        }
+    }
+
+    /// Add the fields of a generated key, or record why generating it failed.
+    fn generated_key(
+        mut self,
+        generated: ssh_key::Result<(PrivateKey, Zeroizing<String>)>,
+    ) -> Self {
+        match generated {
+            Ok((key, openssh)) => self.ssh_key_fields(&openssh, Some(&key)),
+            Err(err) => {
+                error!("Generating an Ed25519 key failed: {}", err);
+                self.problems.push(ValidationProblem::new(
+                    "private key",
+                    ValidationCode::KeyGenerationFailed,
+                ));
+                self
+            }
+        }
+    }
+}
+
+/// Builds an SSH Key item
+///
+/// The public key and its SHA256 fingerprint are added alongside the private key.
+///
+/// ```
+/// use connect_1password::models::{
+///     item::{FullItem, ItemBuilder},
+///     VaultId,
+/// };
+///
+/// let vault_id: VaultId = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+/// let item: FullItem = ItemBuilder::ssh_key(&vault_id)
+///     .title("Deploy key")
+///     .generate_ed25519_key("deploy@example.com")
+///     .build()
+///     .unwrap();
+///
+/// let public_key = item.field_by_id("public_key").unwrap();
+/// assert!(public_key.value.as_ref().unwrap().expose().starts_with("ssh-ed25519 "));
+/// ```
+impl<T> ItemBuilder<SshKey, T> {
+    /// Specify an existing private key in OpenSSH format
+    pub fn private_key(self, openssh: &str) -> Self {
+        let key = PrivateKey::from_openssh(openssh).ok();
+
+        self.ssh_key_fields(openssh, key.as_ref())
+    }
+
+    /// Generate a new Ed25519 key pair locally, with the given comment
+    ///
+    /// If generating the key fails, `build` reports it.
+    pub fn generate_ed25519_key(self, comment: &str) -> Self {
+        let generated = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).and_then(|mut key| {
+            key.set_comment(comment);
+            let openssh = key.to_openssh(LineEnding::LF)?;
+            Ok((key, openssh))
+        });
+
+        self.generated_key(generated)
+    }
+}
+
+impl ItemBuilder<SshKey, Titled> {
+    /// Execute the builder
+    pub fn build(&self) -> Result<FullItem, Error> {
+        let mut problems: Vec<_> = self.title_problem().into_iter().collect();
+        let generation_failed = self
+            .problems
+            .iter()
+            .any(|problem| problem.code == ValidationCode::KeyGenerationFailed);
+        if !generation_failed && !self.has_field(|field| field.r#type == Some(FieldType::SshKey)) {
+            problems.push(ValidationProblem::new(
+                "private key",
+                ValidationCode::MissingRequiredField,
+            ));
+        }
+
+        self.finish(problems)
+    }
+}
+
+impl FullItem {
+    /// Returns the private key of an SSH Key item.
+    pub fn private_key(&self) -> Result<&SecretString, Error> {
+        self.find_value(&format!("type {}", FieldType::SshKey), |field| {
+            field.r#type == Some(FieldType::SshKey)
+        })
+    }
+
+    /// Write the private key of an SSH Key item to `path`, readable and writable only by the
+    /// owner (mode `0600`) on Unix.
+    ///
+    /// An existing file is overwritten and its permissions are tightened.
+    pub fn write_private_key<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let key = self.private_key()?.expose();
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all(key.as_bytes())?;
+        if !key.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a problem if an `SSHKEY` field does not hold an OpenSSH private key.
+pub(super) fn field_problem(field: &FieldObject) -> Option<ValidationCode> {
+    let value = field.value.as_ref().map(SecretString::expose)?;
+    match field.r#type {
+        Some(FieldType::SshKey) if PrivateKey::from_openssh(value).is_err() => {
+            Some(ValidationCode::InvalidSshKey)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn builder() -> ItemBuilder<SshKey, Titled> {
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        ItemBuilder::ssh_key(&vault_id).title("Deploy key")
+    }
+
+    fn value<'a>(item: &'a FullItem, id: &str) -> &'a str {
+        item.field_by_id(id)
+            .unwrap()
+            .value
+            .as_ref()
+            .map(SecretString::expose)
+            .unwrap()
+    }
+
+    #[test]
+    fn generated_key() {
+        let item = builder()
+            .generate_ed25519_key("deploy@example.com")
+            .build()
+            .unwrap();
+
+        let private_key = PrivateKey::from_openssh(value(&item, "private_key")).unwrap();
+        assert_eq!(private_key.algorithm(), Algorithm::Ed25519);
+        assert_eq!(
+            value(&item, "public_key"),
+            private_key.public_key().to_openssh().unwrap()
+        );
+        assert!(value(&item, "public_key").ends_with(" deploy@example.com"));
+        assert!(value(&item, "fingerprint").starts_with("SHA256:"));
+        assert_eq!(
+            item.field_by_id("private_key").unwrap().r#type,
+            Some(FieldType::SshKey)
+        );
+    }
+
+    #[test]
+    fn failed_generation() {
+        let err = builder()
+            .generated_key(Err(ssh_key::Error::Crypto))
+            .build()
+            .unwrap_err();
+        let problems = &err.validation_error().unwrap().problems;
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "private key");
+        assert_eq!(problems[0].code, ValidationCode::KeyGenerationFailed);
+    }
+
+    #[test]
+    fn existing_key() {
+        let generated = PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap();
+        let openssh = generated.to_openssh(LineEnding::LF).unwrap();
+
+        let item = builder().private_key(&openssh).build().unwrap();
+
+        assert_eq!(item.private_key().unwrap().expose(), openssh.as_str());
+        assert_eq!(
+            value(&item, "fingerprint"),
+            generated.fingerprint(HashAlg::Sha256).to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_key() {
+        let err = builder().private_key("not a key").build().unwrap_err();
+        let problems = &err.validation_error().unwrap().problems;
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].field, "private key");
+        assert_eq!(problems[0].code, ValidationCode::InvalidSshKey);
+
+        let err = builder().build().unwrap_err();
+        assert!(err
+            .validation_error()
+            .unwrap()
+            .has(ValidationCode::MissingRequiredField));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_key() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let item = builder().generate_ed25519_key("").build().unwrap();
+        let path = std::env::temp_dir().join(format!("id_ed25519_{}", uuid::Uuid::new_v4()));
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        item.write_private_key(&path).unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(written, item.private_key().unwrap().expose());
+    }
+}
@@ -1,21 +1,200 @@
 //! Models
 
+/// Vault and item IDs
+pub mod id;
 /// Item related models
 pub mod item;
 /// Vault related models
 pub mod vault;
 
+pub use id::*;
 pub use item::*;
 pub use vault::*;
 
-/// This is a wrapper to assist creating instances of `ConnectAPIError`
-#[derive(Debug)]
-pub struct StatusWrapper {
-    pub(crate) status: u16,
-}
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Value;
+    use std::{collections::BTreeSet, fmt::Debug};
+
+    const VAULTS: &str = r#"[
+        {
+            "id": "ytrfte14kw1uex5txn3pnaoerm",
+            "name": "Demo",
+            "attributeVersion": 1,
+            "contentVersion": 72,
+            "items": 7,
+            "type": "USER_CREATED",
+            "createdAt": "2021-04-10T17:34:26Z",
+            "updatedAt": "2021-04-13T14:33:50Z"
+        },
+        {
+            "id": "ftz4pm2xxwmwrsd7rjqn7grzfz",
+            "name": "Private",
+            "description": "Your private vault",
+            "attributeVersion": 2,
+            "contentVersion": 5,
+            "items": 0,
+            "type": "PERSONAL",
+            "createdAt": "2021-04-10T17:34:26Z",
+            "updatedAt": "2021-04-10T17:34:26Z"
+        }
+    ]"#;
+
+    const ITEMS: &str = r#"[
+        {
+            "id": "2fcbqwe9ndg175zg2dzwftvkpa",
+            "title": "Secrets Automation Item",
+            "tags": ["connect", "🐧"],
+            "vault": {"id": "ftz4pm2xxwmwrsd7rjqn7grzfz"},
+            "category": "LOGIN",
+            "urls": [{"primary": true, "href": "https://connect.1password.io"}],
+            "favorite": false,
+            "version": 2,
+            "state": "ARCHIVED",
+            "lastEditedBy": "LUGLUC5FUZGRPPAIPI6U3HJDRQ",
+            "createdAt": "2021-04-10T17:20:05.98944527Z",
+            "updatedAt": "2021-04-13T17:20:05.989445411Z"
+        }
+    ]"#;
+
+    const FULL_ITEM: &str = r#"{
+        "id": "2fcbqwe9ndg175zg2dzwftvkpa",
+        "title": "Secrets Automation Item",
+        "tags": ["connect", "🐧"],
+        "vault": {"id": "ftz4pm2xxwmwrsd7rjqn7grzfz"},
+        "category": "LOGIN",
+        "urls": [{"label": "website", "primary": true, "href": "https://connect.1password.io"}],
+        "favorite": true,
+        "version": 2,
+        "sections": [
+            {"id": "95cdbc3b-7742-47ec-9056-44d6af82d562", "label": "Security Questions"},
+            {"id": "add more"}
+        ],
+        "fields": [
+            {
+                "id": "username",
+                "type": "STRING",
+                "purpose": "USERNAME",
+                "label": "username",
+                "value": "wendy",
+                "reference": "op://Demo/Secrets Automation Item/username"
+            },
+            {
+                "id": "password",
+                "type": "CONCEALED",
+                "purpose": "PASSWORD",
+                "label": "password",
+                "value": "RGfacVQ2YCZ8m4kEQpkmKaVgnmtuAqTm",
+                "entropy": 189.78359985351562,
+                "passwordDetails": {"entropy": 189, "generated": true, "strength": "FANTASTIC"}
+            },
+            {
+                "id": "notesPlain",
+                "type": "STRING",
+                "purpose": "NOTES",
+                "label": "notesPlain"
+            },
+            {
+                "id": "a6cvmeqakbxoflkgmor4haji7y",
+                "section": {"id": "95cdbc3b-7742-47ec-9056-44d6af82d562"},
+                "type": "STRING",
+                "label": "First pet",
+                "value": "Mr. Whiskers"
+            },
+            {
+                "id": "TOTP_6ba4yk7pmb2jhbbuqrzrwzbfhu",
+                "section": {"id": "add more"},
+                "type": "OTP",
+                "label": "one-time password",
+                "value": "otpauth://totp/Demo?secret=JBSWY3DPEHPK3PXP",
+                "totp": "996554"
+            }
+        ],
+        "lastEditedBy": "LUGLUC5FUZGRPPAIPI6U3HJDRQ",
+        "createdAt": "2021-04-10T17:20:05.98944527Z",
+        "updatedAt": "2021-04-13T17:20:05.989445411Z"
+    }"#;
+
+    /// Collect the path of every object key in `value`.
+    fn keys(value: &Value, path: &str, out: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let path = format!("{}.{}", path, key);
+                    keys(value, &path, out);
+                    out.insert(path);
+                }
+            }
+            Value::Array(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    keys(value, &format!("{}[{}]", path, i), out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Deserialize `json`, serialize it again and check nothing was lost or renamed.
+    fn round_trip<T>(json: &str) -> T
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let recorded: Value = serde_json::from_str(json).unwrap();
+        let parsed: T = serde_json::from_str(json).unwrap();
+        let serialized = serde_json::to_value(&parsed).unwrap();
+
+        let (mut expected, mut actual) = (BTreeSet::new(), BTreeSet::new());
+        keys(&recorded, "", &mut expected);
+        keys(&serialized, "", &mut actual);
+        assert_eq!(actual, expected);
+
+        let reparsed: T = serde_json::from_value(serialized).unwrap();
+        assert_eq!(reparsed, parsed);
+
+        parsed
+    }
+
+    #[test]
+    fn vaults_round_trip() {
+        let vaults: Vec<VaultData> = round_trip(VAULTS);
+
+        assert_eq!(vaults[0].r#type, VaultType::UserCreated);
+        assert_eq!(vaults[0].items, Some(7));
+        assert_eq!(vaults[0].attribute_version, 1);
+        assert_eq!(vaults[1].r#type, VaultType::Personal);
+        assert_eq!(vaults[1].description.as_deref(), Some("Your private vault"));
+    }
+
+    #[test]
+    fn items_round_trip() {
+        let items: Vec<ItemData> = round_trip(ITEMS);
+
+        assert_eq!(items[0].version, Some(2));
+        assert!(items[0].created_at.is_some());
+        assert_eq!(
+            items[0].urls.as_ref().unwrap()[0].url,
+            "https://connect.1password.io"
+        );
+    }
+
+    #[test]
+    fn full_item_round_trip() {
+        let item: FullItem = round_trip(FULL_ITEM);
+
+        assert_eq!(item.id.as_ref().unwrap(), "2fcbqwe9ndg175zg2dzwftvkpa");
+        assert_eq!(item.username().unwrap().expose(), "wendy");
+        assert_eq!(item.urls()[0].label.as_deref(), Some("website"));
+    }
+
+    #[test]
+    fn vault_type() {
+        let vault_type: VaultType = serde_json::from_str(r#""EVERYONE""#).unwrap();
+        assert_eq!(vault_type, VaultType::Everyone);
 
-impl Into<String> for StatusWrapper {
-    fn into(self) -> String {
-        self.status.to_string()
+        let vault_type: VaultType = serde_json::from_str(r#""SYSTEM""#).unwrap();
+        assert_eq!(vault_type, VaultType::Unknown("SYSTEM".to_string()));
+        assert_eq!(serde_json::to_string(&vault_type).unwrap(), r#""SYSTEM""#);
     }
 }
@@ -1,27 +1,92 @@
+use super::VaultId;
 use chrono::{DateTime, Utc};
-use hyper::StatusCode;
-use log::debug;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 /// Defines a Vault object
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct VaultData {
-    /// The UUID of the vault.
-    pub id: String,
+    /// The ID of the vault.
+    pub id: VaultId,
     /// The name of the vault.
     pub name: String,
     /// The description of the vault.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// The version of the vault metadata.
     pub attribute_version: u32,
     /// The version of the vault contents.
     pub content_version: u32,
+    /// The number of items in the vault.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<u32>,
     /// The type of vault.
-    pub r#type: String,
+    pub r#type: VaultType,
     /// Date and time when the vault was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
     /// Date and time when the vault or its contents were last changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+/// Describes the type of a vault
+///
+/// Types unknown to this version of the SDK are kept as [`VaultType::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum VaultType {
+    /// A vault created by a user
+    UserCreated,
+    /// A user's Personal or Private vault
+    Personal,
+    /// The Shared vault everyone in the account has access to
+    Everyone,
+    /// A vault used to transfer items between accounts
+    Transfer,
+    /// A type not known to this SDK, holding the raw value
+    Unknown(String),
+}
+
+impl VaultType {
+    /// Returns the value used by the Connect API, e.g. `USER_CREATED`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::UserCreated => "USER_CREATED",
+            Self::Personal => "PERSONAL",
+            Self::Everyone => "EVERYONE",
+            Self::Transfer => "TRANSFER",
+            Self::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<&str> for VaultType {
+    fn from(value: &str) -> Self {
+        match value {
+            "USER_CREATED" => Self::UserCreated,
+            "PERSONAL" => Self::Personal,
+            "EVERYONE" => Self::Everyone,
+            "TRANSFER" => Self::Transfer,
+            _ => Self::Unknown(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for VaultType {
+    fn from(value: String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+impl From<VaultType> for String {
+    fn from(vault_type: VaultType) -> Self {
+        vault_type.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for VaultType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
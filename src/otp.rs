@@ -0,0 +1,459 @@
+//! One-time passwords
+//!
+//! Parses `otpauth://totp/...` URIs and base32 secrets, as stored in `OTP` fields, and computes
+//! [RFC 6238](https://datatracker.ietf.org/doc/html/rfc6238) codes locally.
+//!
+//! ```
+//! use connect_1password::otp::Totp;
+//!
+//! let totp: Totp = "otpauth://totp/ACME:bob@example.com?secret=JBSWY3DPEHPK3PXP&issuer=ACME"
+//!     .parse()
+//!     .unwrap();
+//! assert_eq!(totp.issuer.as_deref(), Some("ACME"));
+//! assert_eq!(totp.generate_at(59).unwrap().expose(), "996554");
+//! ```
+
+use crate::{
+    error::{Error, ValidationCode, ValidationError, ValidationProblem},
+    models::item::{FieldObject, FieldType},
+    secret::SecretString,
+};
+use hmac::{digest::KeyInit, Hmac, Mac};
+use percent_encoding::percent_decode_str;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use url::Url;
+use zeroize::Zeroizing;
+
+/// Fewest digits accepted in a code.
+pub const MIN_DIGITS: u32 = 6;
+/// Most digits accepted in a code.
+pub const MAX_DIGITS: u32 = 8;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// HMAC algorithm used to compute codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Algorithm {
+    /// HMAC-SHA1, the default
+    #[default]
+    Sha1,
+    /// HMAC-SHA256
+    Sha256,
+    /// HMAC-SHA512
+    Sha512,
+}
+
+impl Algorithm {
+    /// Returns the name used in `otpauth://` URIs, e.g. `SHA1`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+            Self::Sha512 => "SHA512",
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_uppercase().as_str() {
+            "SHA1" => Ok(Self::Sha1),
+            "SHA256" => Ok(Self::Sha256),
+            "SHA512" => Ok(Self::Sha512),
+            _ => Err(invalid("otp.algorithm")),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A time-based one-time password generator.
+///
+/// Parse one from an `otpauth://totp/...` URI or a base32 secret with [`str::parse`], or from an
+/// `OTP` field with `TryFrom<&FieldObject>`. The secret is wiped on drop and never printed.
+#[derive(Clone)]
+pub struct Totp {
+    secret: Zeroizing<Vec<u8>>,
+    /// Number of digits in a code.
+    pub digits: u32,
+    /// Seconds each code is valid for.
+    pub period: u64,
+    /// HMAC algorithm.
+    pub algorithm: Algorithm,
+    /// The issuer, from the URI's label or `issuer` parameter.
+    pub issuer: Option<String>,
+    /// The account name, from the URI's label.
+    pub account: Option<String>,
+}
+
+impl Totp {
+    /// Create a generator for the raw `secret`, producing 6 digit codes every 30 seconds using
+    /// SHA1.
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            secret: Zeroizing::new(secret.to_vec()),
+            digits: MIN_DIGITS,
+            period: 30,
+            algorithm: Algorithm::default(),
+            issuer: None,
+            account: None,
+        }
+    }
+
+    /// Create a generator for a base32 encoded secret. Case, spaces and padding are ignored.
+    pub fn from_base32(secret: &str) -> Result<Self, Error> {
+        let secret = decode_base32(secret).ok_or_else(|| invalid("otp.secret"))?;
+        let totp = Self::new(&secret);
+        totp.validate()?;
+
+        Ok(totp)
+    }
+
+    /// Create a generator from an `otpauth://totp/...` URI.
+    pub fn from_uri(uri: &str) -> Result<Self, Error> {
+        let url = Url::parse(uri).map_err(|_| invalid("otp.uri"))?;
+        if url.scheme() != "otpauth" || url.host_str() != Some("totp") {
+            return Err(invalid("otp.uri"));
+        }
+
+        let label = percent_decode_str(url.path().trim_start_matches('/')).decode_utf8_lossy();
+        let (label_issuer, account) = match label.split_once(':') {
+            Some((issuer, account)) => (Some(issuer.trim()), account.trim()),
+            None => (None, label.trim()),
+        };
+
+        let mut secret = None;
+        let mut totp = Self::new(&[]);
+        totp.issuer = label_issuer.map(str::to_string);
+        totp.account = (!account.is_empty()).then(|| account.to_string());
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "secret" => secret = Some(Zeroizing::new(value.into_owned())),
+                "issuer" => totp.issuer = Some(value.into_owned()),
+                "algorithm" => totp.algorithm = value.parse()?,
+                "digits" => totp.digits = value.parse().map_err(|_| invalid("otp.digits"))?,
+                "period" => totp.period = value.parse().map_err(|_| invalid("otp.period"))?,
+                _ => {}
+            }
+        }
+
+        let secret = secret.ok_or_else(|| invalid("otp.secret"))?;
+        totp.secret = Zeroizing::new(decode_base32(&secret).ok_or_else(|| invalid("otp.secret"))?);
+        totp.validate()?;
+
+        Ok(totp)
+    }
+
+    /// Set the number of digits in a code.
+    pub fn digits(mut self, digits: u32) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Set the number of seconds each code is valid for.
+    pub fn period(mut self, period: u64) -> Self {
+        self.period = period;
+        self
+    }
+
+    /// Set the HMAC algorithm.
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Compute the code for the current time.
+    pub fn generate(&self) -> Result<SecretString, Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        self.generate_at(now.as_secs())
+    }
+
+    /// Compute the code for `unix_time`, in seconds since the Unix epoch.
+    pub fn generate_at(&self, unix_time: u64) -> Result<SecretString, Error> {
+        self.validate()?;
+
+        let counter = (unix_time / self.period).to_be_bytes();
+        let digest = match self.algorithm {
+            Algorithm::Sha1 => hmac::<Hmac<Sha1>>(&self.secret, &counter),
+            Algorithm::Sha256 => hmac::<Hmac<Sha256>>(&self.secret, &counter),
+            Algorithm::Sha512 => hmac::<Hmac<Sha512>>(&self.secret, &counter),
+        };
+
+        // Dynamic truncation, RFC 4226 section 5.3
+        let offset = usize::from(digest[digest.len() - 1] & 0x0f);
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+        let code = u64::from(binary) % 10u64.pow(self.digits);
+
+        Ok(SecretString::new(format!(
+            "{:0width$}",
+            code,
+            width = self.digits as usize
+        )))
+    }
+
+    /// Seconds until the current code expires.
+    pub fn time_remaining(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        self.period - now.as_secs() % self.period.max(1)
+    }
+
+    pub(crate) fn problems(&self) -> Vec<ValidationProblem> {
+        let mut problems = vec![];
+        if self.secret.is_empty() {
+            problems.push(ValidationProblem::new(
+                "otp.secret",
+                ValidationCode::InvalidOtp,
+            ));
+        }
+        if !(MIN_DIGITS..=MAX_DIGITS).contains(&self.digits) {
+            problems.push(ValidationProblem::new(
+                "otp.digits",
+                ValidationCode::InvalidOtp,
+            ));
+        }
+        if self.period == 0 {
+            problems.push(ValidationProblem::new(
+                "otp.period",
+                ValidationCode::InvalidOtp,
+            ));
+        }
+
+        problems
+    }
+
+    /// Check the secret, digits and period.
+    pub fn validate(&self) -> Result<(), Error> {
+        let problems = self.problems();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new_validation_error(ValidationError::new(problems)))
+        }
+    }
+}
+
+impl FromStr for Totp {
+    type Err = Error;
+
+    /// Parse an `otpauth://totp/...` URI, or else a base32 secret.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let scheme = value.get(..10);
+        if scheme.is_some_and(|scheme| scheme.eq_ignore_ascii_case("otpauth://")) {
+            Self::from_uri(value)
+        } else {
+            Self::from_base32(value)
+        }
+    }
+}
+
+impl TryFrom<&FieldObject> for Totp {
+    type Error = Error;
+
+    fn try_from(field: &FieldObject) -> Result<Self, Self::Error> {
+        match (&field.r#type, &field.value) {
+            (Some(FieldType::Otp), Some(value)) => value.expose().parse(),
+            _ => Err(invalid("otp")),
+        }
+    }
+}
+
+impl fmt::Debug for Totp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Totp")
+            .field("secret", &crate::secret::REDACTED)
+            .field("digits", &self.digits)
+            .field("period", &self.period)
+            .field("algorithm", &self.algorithm)
+            .field("issuer", &self.issuer)
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+fn invalid(field: &str) -> Error {
+    Error::new_validation_error(ValidationError::new(vec![ValidationProblem::new(
+        field,
+        ValidationCode::InvalidOtp,
+    )]))
+}
+
+fn hmac<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Decode RFC 4648 base32, ignoring case, spaces, dashes and padding.
+fn decode_base32(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in value.bytes() {
+        if matches!(c, b' ' | b'-' | b'=') {
+            continue;
+        }
+        let index = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | index as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    (!bytes.is_empty()).then_some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::item::ItemBuilder;
+
+    const SHA1_SEED: &[u8] = b"12345678901234567890";
+    const SHA256_SEED: &[u8] = b"12345678901234567890123456789012";
+    const SHA512_SEED: &[u8] = b"1234567890123456789012345678901234567890123456789012345678901234";
+
+    #[test]
+    fn rfc6238_test_vectors() {
+        // RFC 6238, Appendix B
+        let vectors = [
+            (59, "94287082", "46119246", "90693936"),
+            (1111111109, "07081804", "68084774", "25091201"),
+            (1111111111, "14050471", "67062674", "99943326"),
+            (1234567890, "89005924", "91819424", "93441116"),
+            (2000000000, "69279037", "90698825", "38618901"),
+            (20000000000, "65353130", "77737706", "47863826"),
+        ];
+
+        let sha1 = Totp::new(SHA1_SEED).digits(8);
+        let sha256 = Totp::new(SHA256_SEED)
+            .digits(8)
+            .algorithm(Algorithm::Sha256);
+        let sha512 = Totp::new(SHA512_SEED)
+            .digits(8)
+            .algorithm(Algorithm::Sha512);
+        for (time, sha1_code, sha256_code, sha512_code) in vectors {
+            assert_eq!(sha1.generate_at(time).unwrap().expose(), sha1_code);
+            assert_eq!(sha256.generate_at(time).unwrap().expose(), sha256_code);
+            assert_eq!(sha512.generate_at(time).unwrap().expose(), sha512_code);
+        }
+    }
+
+    #[test]
+    fn base32_secret() {
+        // "12345678901234567890" in base32
+        let totp: Totp = "gezd gnbv gy3t qojq gezd gnbv gy3t qojq".parse().unwrap();
+
+        assert_eq!(totp.generate_at(59).unwrap().expose(), "287082");
+        assert!("not base32!".parse::<Totp>().is_err());
+        assert!("".parse::<Totp>().is_err());
+    }
+
+    #[test]
+    fn otpauth_uri() {
+        let totp: Totp = "otpauth://totp/ACME%20Co:john@example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA&algorithm=SHA256&digits=8&period=60"
+            .parse()
+            .unwrap();
+
+        assert_eq!(totp.issuer.as_deref(), Some("ACME Co"));
+        assert_eq!(totp.account.as_deref(), Some("john@example.com"));
+        assert_eq!(totp.algorithm, Algorithm::Sha256);
+        assert_eq!(totp.digits, 8);
+        assert_eq!(totp.period, 60);
+        assert_eq!(totp.generate_at(118).unwrap().expose(), "46119246");
+    }
+
+    #[test]
+    fn invalid_uris() {
+        for uri in [
+            "otpauth://hotp/ACME?secret=JBSWY3DPEHPK3PXP",
+            "otpauth://totp/ACME",
+            "otpauth://totp/ACME?secret=JBSWY3DPEHPK3PXP&digits=12",
+            "otpauth://totp/ACME?secret=JBSWY3DPEHPK3PXP&period=0",
+            "otpauth://totp/ACME?secret=JBSWY3DPEHPK3PXP&algorithm=MD5",
+        ] {
+            let err = uri.parse::<Totp>().unwrap_err();
+            assert!(
+                err.validation_error()
+                    .unwrap()
+                    .has(ValidationCode::InvalidOtp),
+                "{}",
+                uri
+            );
+        }
+    }
+
+    #[test]
+    fn non_ascii_input() {
+        for value in ["123456789é", "otpauth:/é/totp/ACME"] {
+            let err = value.parse::<Totp>().unwrap_err();
+            assert!(
+                err.validation_error()
+                    .unwrap()
+                    .has(ValidationCode::InvalidOtp),
+                "{}",
+                value
+            );
+        }
+
+        let vault_id = "ytrfte14kw1uex5txn3pnaoerm".parse().unwrap();
+        let err = ItemBuilder::login(&vault_id)
+            .title("Login")
+            .password("hunter2")
+            .add_otp("123456789é")
+            .build()
+            .unwrap_err();
+        assert!(err
+            .validation_error()
+            .unwrap()
+            .has(ValidationCode::InvalidOtp));
+    }
+
+    #[test]
+    fn from_field() {
+        let field = FieldObject {
+            r#type: Some(FieldType::Otp),
+            value: Some(SecretString::from(
+                "otpauth://totp/ACME:bob?secret=JBSWY3DPEHPK3PXP",
+            )),
+            ..Default::default()
+        };
+        let totp = Totp::try_from(&field).unwrap();
+
+        assert_eq!(totp.account.as_deref(), Some("bob"));
+        assert_eq!(totp.generate().unwrap().len(), 6);
+        assert!(!format!("{:?}", totp).contains("JBSWY3DPEHPK3PXP"));
+
+        let field = FieldObject {
+            r#type: Some(FieldType::String),
+            ..field
+        };
+        assert!(Totp::try_from(&field).is_err());
+    }
+}
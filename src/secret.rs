@@ -0,0 +1,102 @@
+//! Secret values
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Printed in place of a secret by `Debug` and `Display`.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A string holding a secret, such as a password or the API token.
+///
+/// `Debug` and `Display` print [`REDACTED`] instead of the value and the memory is wiped when
+/// the value is dropped. Use [`SecretString::expose`] to read it. Serializes as a plain string.
+///
+/// ```
+/// use connect_1password::secret::SecretString;
+///
+/// let password = SecretString::from("hunter2");
+/// assert_eq!(format!("{:?}", password), "[REDACTED]");
+/// assert_eq!(password.expose(), "hunter2");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+/// An empty secret, standing in for values Connect omits.
+pub(crate) static EMPTY: SecretString = SecretString(String::new());
+
+impl SecretString {
+    /// Create a new secret.
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    /// Returns the secret value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns true if the secret is an empty string.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the length of the secret, in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SecretString, REDACTED};
+
+    #[test]
+    fn redacted() {
+        let secret = SecretString::from("hunter2");
+
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(secret.to_string(), REDACTED);
+        assert_eq!(format!("{:?}", Some(secret.clone())), "Some([REDACTED])");
+        assert_eq!(secret.expose(), "hunter2");
+    }
+
+    #[test]
+    fn serde_is_transparent() {
+        let secret: SecretString = serde_json::from_str(r#""hunter2""#).unwrap();
+
+        assert_eq!(secret.expose(), "hunter2");
+        assert_eq!(serde_json::to_string(&secret).unwrap(), r#""hunter2""#);
+    }
+}
@@ -1,100 +1,64 @@
 //! Vaults
 
-use crate::error::{ConnectAPIError, Error};
+use crate::error::Error;
 use crate::{
-    client::HTTPClient,
-    models::{StatusWrapper, VaultData},
-    *,
+    client::{CallOptions, HTTPClient, Response, GET},
+    models::{VaultData, VaultId},
 };
 
 /// Get all known vaults
-pub async fn all(client: &impl HTTPClient) -> Result<(Vec<VaultData>, serde_json::Value), Error> {
+pub async fn all(client: &impl HTTPClient) -> Result<Response<Vec<VaultData>>, Error> {
+    all_with_options(client, &CallOptions::default()).await
+}
+
+/// Get all known vaults, applying the given [`CallOptions`]
+pub async fn all_with_options(
+    client: &impl HTTPClient,
+    options: &CallOptions,
+) -> Result<Response<Vec<VaultData>>, Error> {
     let params = vec![("", "")];
 
-    let result = match client
-        .send_request::<Vec<VaultData>>("GET", "v1/vaults", &params, None)
+    client
+        .send_request::<Vec<VaultData>>("GET", "v1/vaults", &params, None, options)
         .await
-    {
-        Ok(value) => value,
-        Err(err) => {
-            let op_error = crate::error::process_connect_error_response(err.to_string())?;
-
-            let message = "Invalid bearer token";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            return Err(Error::new_internal_error().with(err));
-        }
-    };
-
-    Ok(result)
+        .map_err(|err| err.with_operation("vaults::all", GET, "v1/vaults"))
 }
 
 /// Get vault details
-pub async fn get(
+pub async fn get(client: &impl HTTPClient, id: &VaultId) -> Result<Response<VaultData>, Error> {
+    get_with_options(client, id, &CallOptions::default()).await
+}
+
+/// Get vault details, applying the given [`CallOptions`]
+pub async fn get_with_options(
     client: &impl HTTPClient,
-    id: &str,
-) -> Result<(VaultData, serde_json::Value), Error> {
+    id: &VaultId,
+    options: &CallOptions,
+) -> Result<Response<VaultData>, Error> {
     let params = vec![("", "")];
     let path = format!("v1/vaults/{}", id);
 
-    let result = match client
-        .send_request::<VaultData>("GET", &path, &params, None)
+    client
+        .send_request::<VaultData>("GET", &path, &params, None, options)
         .await
-    {
-        Ok(value) => value,
-        Err(err) => {
-            let op_error = crate::error::process_connect_error_response(err.to_string())?;
-
-            let mut message = "Invalid bearer token";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            message = "Invalid Vault UUID";
-            if err.to_string().contains(message) {
-                let status = StatusWrapper {
-                    status: op_error.status_code.unwrap_or_default(),
-                };
-
-                return Err(Error::new_connect_error(ConnectAPIError::new(
-                    status.into(),
-                    message,
-                )));
-            }
-
-            return Err(Error::new_internal_error().with(err));
-        }
-    };
-
-    Ok(result)
+        .map_err(|err| {
+            err.with_operation("vaults::get", GET, "v1/vaults/{vault_id}")
+                .with_vault_id(id.as_str())
+        })
 }
 
 #[cfg(test)]
 mod test {
-    use super::*;
+    use crate::{
+        error::ValidationCode, get_test_client, get_test_vault_id, models::VaultId, vaults,
+    };
     use tokio::test;
 
     #[test]
     async fn all() {
         let client = get_test_client();
 
-        let (vaults, _) = vaults::all(&client).await.unwrap();
+        let vaults = vaults::all(&client).await.unwrap().into_value();
         dbg!(&vaults);
 
         assert_eq!(vaults[0].name, "Automated".to_string());
@@ -103,30 +67,36 @@ mod test {
     #[test]
     async fn get() {
         let client = get_test_client();
-        let test_vault_id =
-            std::env::var("OP_TESTING_VAULT_ID").expect("1Password Vault ID for testing");
+        let test_vault_id = get_test_vault_id();
 
-        let (vault, _) = vaults::get(&client, &test_vault_id).await.unwrap();
+        let vault = vaults::get(&client, &test_vault_id)
+            .await
+            .unwrap()
+            .into_value();
         dbg!(&vault);
 
         assert_eq!(vault.name, "Automated".to_string());
     }
 
-    #[should_panic]
     #[test]
     async fn get_vault_details_not_specified() {
-        let client = get_test_client();
-
-        let (vault, _) = vaults::get(&client, "").await.unwrap();
+        let err = "".parse::<VaultId>().unwrap_err();
 
-        assert_eq!(vault.name, "Automated".to_string());
+        assert!(err
+            .validation_error()
+            .unwrap()
+            .has(ValidationCode::InvalidId));
     }
 
     #[should_panic]
     #[test]
     async fn get_vault_details_invalid_vault() {
         let client = get_test_client();
+        let unknown_vault_id: VaultId = "aaaaaaaaaaaaaaaaaaaaaaaaaa".parse().unwrap();
 
-        let (_vault, _) = vaults::get(&client, "foo").await.unwrap();
+        let _vault = vaults::get(&client, &unknown_vault_id)
+            .await
+            .unwrap()
+            .into_value();
     }
 }